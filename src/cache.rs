@@ -0,0 +1,307 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, OnceLock},
+    time::SystemTime,
+};
+
+use chrono::NaiveDate;
+
+use crate::span::{self, SpanListArray};
+use crate::validate::validate_spans;
+
+static SPANS_CACHE: OnceLock<Mutex<HashMap<PathBuf, CachedSpans>>> = OnceLock::new();
+
+type ModifiedTimes = Vec<(PathBuf, Option<SystemTime>)>;
+
+struct CachedSpans {
+    spans: Arc<SpanListArray>,
+    modified: ModifiedTimes,
+    // `****`は読み込んだ日の日付になるので、日付が変われば読み込み直す
+    loaded_date: NaiveDate,
+}
+
+/// `path`(`gengou_lists`)内のファイルを読み込む。
+/// 読み込み済みで、ファイルが更新されていなければキャッシュを返す。
+pub(crate) fn load_spans(path: &Path) -> Result<Arc<SpanListArray>, std::io::Error> {
+    let modified = modified_times(path)?;
+    let today = chrono::Local::now().date_naive();
+
+    let mut cache = lock_cache();
+    if let Some(cached) = cache.get(path) {
+        if cached.modified == modified && cached.loaded_date == today {
+            return Ok(cached.spans.clone());
+        }
+    }
+
+    let spans = Arc::new(span::load_spans(path)?);
+    cache.insert(
+        path.to_path_buf(),
+        CachedSpans {
+            spans: spans.clone(),
+            modified,
+            loaded_date: today,
+        },
+    );
+
+    Ok(spans)
+}
+
+/// `path`(`gengou_lists`)内のファイルと、あれば天皇の一覧を必ず読み込み直し、キャッシュを置き換える。
+/// 検証で問題が見つかったときや読み込みに失敗したときは、どちらのキャッシュも以前のまま残る。
+pub(crate) fn reload_spans(path: &Path) -> Result<Arc<SpanListArray>, std::io::Error> {
+    let issues = validate_spans(path)?;
+    if let Some(issue) = issues.first() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{} issue(s) are found. {}", issues.len(), issue),
+        ));
+    }
+
+    let today = chrono::Local::now().date_naive();
+    let mut loaded = vec![load_for_reload(path)?];
+    let tennou_path = path.join(span::TENNOU_DIR_PATH);
+    if tennou_path.is_dir() {
+        let tennou = load_for_reload(&tennou_path).map_err(|e| {
+            std::io::Error::new(e.kind(), format!("{}: {}", span::TENNOU_DIR_PATH, e))
+        })?;
+        loaded.push(tennou);
+    }
+
+    // 全て読み込めてから、まとめて置き換える
+    let spans = loaded[0].1.clone();
+    let mut cache = lock_cache();
+    for (path, spans, modified) in loaded {
+        cache.insert(
+            path,
+            CachedSpans {
+                spans,
+                modified,
+                loaded_date: today,
+            },
+        );
+    }
+
+    Ok(spans)
+}
+
+/// 読み込み直すディレクトリの内容と更新日時。ファイルが一つもなければ失敗とする
+fn load_for_reload(
+    path: &Path,
+) -> Result<(PathBuf, Arc<SpanListArray>, ModifiedTimes), std::io::Error> {
+    let modified = modified_times(path)?;
+    let spans = span::load_spans(path)?;
+    if spans.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "no gengou list files are found.",
+        ));
+    }
+
+    Ok((path.to_path_buf(), Arc::new(spans), modified))
+}
+
+/// `path`(`gengou_lists`)のキャッシュを破棄する
+pub(crate) fn remove(path: &Path) {
+    lock_cache().remove(path);
+}
+
+fn lock_cache() -> MutexGuard<'static, HashMap<PathBuf, CachedSpans>> {
+    SPANS_CACHE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// ディレクトリ内の各ファイルの更新日時の一覧
+fn modified_times(path: &Path) -> Result<ModifiedTimes, std::io::Error> {
+    let mut result = Vec::new();
+    for entry in path.read_dir()? {
+        let entry = entry?;
+        let modified = entry.metadata().and_then(|m| m.modified()).ok();
+        result.push((entry.path(), modified));
+    }
+    result.sort();
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+    use std::time::Duration;
+
+    use super::*;
+
+    fn case_dir(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "japanesegengou_cache_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("001_a.txt"), "a,2000-01-01,2000-12-31\n").unwrap();
+        path
+    }
+
+    mod load_spans {
+        use super::*;
+
+        #[test]
+        fn return_cache_when_files_are_not_modified() {
+            let path = case_dir("not_modified");
+
+            let first = load_spans(&path).unwrap();
+            let second = load_spans(&path).unwrap();
+
+            assert!(Arc::ptr_eq(&first, &second));
+
+            remove(&path);
+            fs::remove_dir_all(&path).unwrap();
+        }
+
+        #[test]
+        fn reload_when_file_is_modified() {
+            let path = case_dir("modified");
+
+            let first = load_spans(&path).unwrap();
+
+            let file_path = path.join("001_a.txt");
+            fs::write(&file_path, "b,2000-01-01,2000-12-31\n").unwrap();
+            let modified = SystemTime::now() + Duration::from_secs(10);
+            File::options()
+                .write(true)
+                .open(&file_path)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+
+            let second = load_spans(&path).unwrap();
+
+            assert!(!Arc::ptr_eq(&first, &second));
+            assert_eq!(second[0].1[&'a'].spans()[0].gengou(), "b");
+
+            remove(&path);
+            fs::remove_dir_all(&path).unwrap();
+        }
+
+        #[test]
+        fn reload_when_file_is_added() {
+            let path = case_dir("added");
+
+            let first = load_spans(&path).unwrap();
+
+            fs::write(path.join("002_a.txt"), "c,2001-01-01,2001-12-31\n").unwrap();
+
+            let second = load_spans(&path).unwrap();
+
+            assert!(!Arc::ptr_eq(&first, &second));
+            assert_eq!(second.len(), 2);
+
+            remove(&path);
+            fs::remove_dir_all(&path).unwrap();
+        }
+
+        #[test]
+        fn failed_when_directory_does_not_exist() {
+            let path = std::env::temp_dir().join("japanesegengou_cache_does_not_exist");
+
+            assert!(load_spans(&path).is_err());
+        }
+    }
+
+    mod reload_spans {
+        use super::*;
+
+        #[test]
+        fn reload_even_if_files_are_not_modified() {
+            let path = case_dir("reload");
+
+            let first = load_spans(&path).unwrap();
+            let second = reload_spans(&path).unwrap();
+            let third = load_spans(&path).unwrap();
+
+            assert!(!Arc::ptr_eq(&first, &second));
+            assert!(Arc::ptr_eq(&second, &third));
+
+            remove(&path);
+            fs::remove_dir_all(&path).unwrap();
+        }
+
+        #[test]
+        fn keep_cache_when_failed() {
+            let path = case_dir("reload_failed");
+
+            let first = load_spans(&path).unwrap();
+
+            fs::write(path.join("001_a.txt"), "a,2000_01-01,2000-12-31\n").unwrap();
+            assert!(reload_spans(&path).is_err());
+
+            let cache = lock_cache();
+            assert!(Arc::ptr_eq(&first, &cache[&path].spans));
+            drop(cache);
+
+            remove(&path);
+            fs::remove_dir_all(&path).unwrap();
+        }
+
+        #[test]
+        fn keep_cache_when_tennou_is_invalid() {
+            let path = case_dir("reload_tennou_failed");
+            let tennou_path = path.join(span::TENNOU_DIR_PATH);
+            fs::create_dir_all(&tennou_path).unwrap();
+            fs::write(tennou_path.join("001_a.txt"), "x,2000-01-01,2000-12-31\n").unwrap();
+
+            let first = load_spans(&path).unwrap();
+            let first_tennou = load_spans(&tennou_path).unwrap();
+
+            fs::write(path.join("001_a.txt"), "b,2000-01-01,2000-12-31\n").unwrap();
+            fs::write(tennou_path.join("001_a.txt"), "x,2000_01-01,2000-12-31\n").unwrap();
+            let e = reload_spans(&path).unwrap_err();
+            assert!(e.to_string().contains("tennou/001_a.txt"), "{}", e);
+
+            let cache = lock_cache();
+            assert!(Arc::ptr_eq(&first, &cache[&path].spans));
+            assert!(Arc::ptr_eq(&first_tennou, &cache[&tennou_path].spans));
+            drop(cache);
+
+            remove(&tennou_path);
+            remove(&path);
+            fs::remove_dir_all(&path).unwrap();
+        }
+
+        #[test]
+        fn keep_cache_when_validation_failed() {
+            let path = case_dir("reload_invalid");
+
+            let first = load_spans(&path).unwrap();
+
+            // 読み込みはできるが、期間が重なっている
+            fs::write(
+                path.join("001_a.txt"),
+                "a,2000-01-01,2000-12-31\nb,2000-06-01,2001-12-31\n",
+            )
+            .unwrap();
+            let e = reload_spans(&path).unwrap_err();
+            assert!(e.to_string().contains("overlap"), "{}", e);
+
+            let cache = lock_cache();
+            assert!(Arc::ptr_eq(&first, &cache[&path].spans));
+            drop(cache);
+
+            remove(&path);
+            fs::remove_dir_all(&path).unwrap();
+        }
+
+        #[test]
+        fn failed_when_no_list_files() {
+            let path = case_dir("reload_empty");
+            fs::remove_file(path.join("001_a.txt")).unwrap();
+
+            assert!(reload_spans(&path).is_err());
+
+            fs::remove_dir_all(&path).unwrap();
+        }
+    }
+}
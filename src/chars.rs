@@ -1,139 +1,166 @@
-use winapi::{
-    shared::{
-        minwindef::LPBOOL,
-        ntdef::{LPCSTR, LPWSTR, NULL},
-    },
-    um::{
-        stringapiset::{MultiByteToWideChar, WideCharToMultiByte},
-        winnls::MB_PRECOMPOSED,
-        winnt::LPSTR,
-    },
-};
-
-pub(crate) fn multi_byte_to_wide_char(from: &[u8], codepage: u32) -> Result<Vec<u16>, ()> {
-    let mut from_buf: Vec<i8> = from.iter().map(|v| *v as i8).collect();
-    from_buf.push(0);
-
-    let to_buf_size = unsafe {
-        MultiByteToWideChar(
-            codepage,
-            MB_PRECOMPOSED,
-            from_buf.as_ptr(),
-            -1,
-            NULL as LPWSTR,
-            0,
-        )
-    };
-
-    if to_buf_size == 0 {
-        return Err(());
-    }
+use std::fmt::Display;
+
+use encoding_rs::{DecoderResult, EncoderResult};
+
+use crate::request::SaoriCharset;
+
+/// 文字コードの変換に失敗したときのエラー
+#[derive(Debug, PartialEq)]
+pub enum CharsetError {
+    /// デコードできないバイト列があった(`position`はバイト単位の位置)
+    Malformed { position: usize, bytes: Vec<u8> },
+    /// 変換先の文字コードで表せない文字があった(`position`は文字単位の位置)
+    Unmappable { position: usize, character: char },
+}
 
-    let mut to_buf = vec![0; to_buf_size as usize + 1];
-    let result = unsafe {
-        MultiByteToWideChar(
-            codepage,
-            MB_PRECOMPOSED,
-            from_buf.as_ptr(),
-            -1,
-            to_buf.as_mut_ptr(),
-            to_buf_size,
-        )
-    };
-
-    if result == 0 {
-        Err(())
-    } else {
-        Ok(to_buf)
+impl Display for CharsetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CharsetError::Malformed { position, bytes } => {
+                write!(
+                    f,
+                    "malformed byte sequence {:02X?} at byte {}",
+                    bytes, position
+                )
+            }
+            CharsetError::Unmappable {
+                position,
+                character,
+            } => write!(
+                f,
+                "unmappable character '{}' (U+{:04X}) at char {}",
+                character, *character as u32, position
+            ),
+        }
     }
 }
 
-pub(crate) fn wide_char_to_multi_byte(from: &mut Vec<u16>, codepage: u32) -> Result<Vec<i8>, ()> {
-    from.push(0);
-
-    let to_buf_size = unsafe {
-        WideCharToMultiByte(
-            codepage,
-            0,
-            from.as_ptr(),
-            -1,
-            NULL as LPSTR,
-            0,
-            NULL as LPCSTR,
-            NULL as LPBOOL,
-        )
-    };
-
-    if to_buf_size == 0 {
-        return Err(());
+/// `charset`で符号化されたバイト列を文字列にする。
+/// 最初のNUL文字より後ろは無視する。
+pub fn decode(from: &[u8], charset: &SaoriCharset) -> Result<String, CharsetError> {
+    let p = from.iter().position(|v| *v == 0).unwrap_or(from.len());
+    let from = &from[..p];
+
+    let mut decoder = charset.encoding().new_decoder_without_bom_handling();
+    let capacity = decoder
+        .max_utf8_buffer_length_without_replacement(from.len())
+        .unwrap_or(from.len() * 3);
+    let mut to_buf = String::with_capacity(capacity);
+
+    let (result, read) = decoder.decode_to_string_without_replacement(from, &mut to_buf, true);
+    match result {
+        DecoderResult::InputEmpty => Ok(to_buf),
+        DecoderResult::Malformed(len, consumed) => {
+            let end = read - consumed as usize;
+            let position = end - len as usize;
+            Err(CharsetError::Malformed {
+                position,
+                bytes: from[position..end].to_vec(),
+            })
+        }
+        DecoderResult::OutputFull => unreachable!("buffer is allocated with max length"),
     }
+}
 
-    let mut to_buf: Vec<i8> = vec![0; to_buf_size as usize + 1];
-    let result = unsafe {
-        WideCharToMultiByte(
-            codepage,
-            0,
-            from.as_ptr(),
-            -1,
-            to_buf.as_mut_ptr(),
-            to_buf_size,
-            NULL as LPCSTR,
-            NULL as LPBOOL,
-        )
-    };
-
-    if result == 0 {
-        Err(())
-    } else {
-        Ok(to_buf)
+/// 文字列を`charset`で符号化されたバイト列にする。
+pub fn encode(from: &str, charset: &SaoriCharset) -> Result<Vec<u8>, CharsetError> {
+    let mut encoder = charset.encoding().new_encoder();
+    let capacity = encoder
+        .max_buffer_length_from_utf8_without_replacement(from.len())
+        .unwrap_or(from.len() * 4);
+    let mut to_buf = Vec::with_capacity(capacity);
+
+    let (result, read) =
+        encoder.encode_from_utf8_to_vec_without_replacement(from, &mut to_buf, true);
+    match result {
+        EncoderResult::InputEmpty => Ok(to_buf),
+        EncoderResult::Unmappable(character) => Err(CharsetError::Unmappable {
+            position: from[..read].chars().count() - 1,
+            character,
+        }),
+        EncoderResult::OutputFull => unreachable!("buffer is allocated with max length"),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::request::SaoriCharset;
-
     use super::*;
 
-    use encoding_rs;
+    const CHARSETS: [SaoriCharset; 4] = [
+        SaoriCharset::ShiftJIS,
+        SaoriCharset::EucJP,
+        SaoriCharset::UTF8,
+        SaoriCharset::ISO2022JP,
+    ];
 
-    mod multi_byte_to_wide_char {
+    mod decode {
         use super::*;
 
         #[test]
-        fn success_when_encoding_and_codepage_is_same() {
+        fn success_when_encoding_and_charset_is_same() {
             let case = "あいうえお仕様";
-            let (case_byte, _encoding, _is_err) = encoding_rs::SHIFT_JIS.encode(case);
+            for charset in CHARSETS.iter() {
+                let (case_byte, _encoding, _is_err) = charset.encoding().encode(case);
 
-            let result =
-                multi_byte_to_wide_char(&case_byte, SaoriCharset::ShiftJIS.codepage()).unwrap();
+                let result = decode(&case_byte, charset).unwrap();
 
-            let p = result.partition_point(|v| *v != 0);
+                assert_eq!(&result, case);
+            }
+        }
+
+        #[test]
+        fn success_and_ignore_after_nul() {
+            let case = "あいう\0えお".as_bytes();
 
-            let result = String::from_utf16_lossy(&result[..p]);
+            let result = decode(case, &SaoriCharset::UTF8).unwrap();
 
-            assert_eq!(&result, case);
+            assert_eq!(&result, "あいう");
+        }
+
+        #[test]
+        fn failed_when_malformed_bytes() {
+            let case = [b'a', b'b', 0x82];
+
+            let result = decode(&case, &SaoriCharset::ShiftJIS);
+
+            assert_eq!(
+                result,
+                Err(CharsetError::Malformed {
+                    position: 2,
+                    bytes: vec![0x82]
+                })
+            );
         }
     }
 
-    mod wide_char_to_multi_byte {
+    mod encode {
         use super::*;
 
         #[test]
-        fn success_when_valid_wide_char_and_codepage_with_shift_jis() {
+        fn success_when_valid_str() {
             let case = "あいうえお仕様";
-            let mut case_chars: Vec<u16> = case.encode_utf16().collect();
-
-            let result =
-                wide_char_to_multi_byte(&mut case_chars, SaoriCharset::ShiftJIS.codepage())
-                    .unwrap();
+            for charset in CHARSETS.iter() {
+                let result = encode(case, charset).unwrap();
 
-            let result: Vec<u8> = result.iter().map(|v| *v as u8).collect();
+                let (decoded, _encoding, _is_err) = charset.encoding().decode(&result);
 
-            let p = result.partition_point(|v| *v != 0);
-            let (encoded, _encoding, _is_err) = encoding_rs::SHIFT_JIS.decode(&result[..p]);
+                assert_eq!(&decoded, case);
+            }
+        }
 
-            assert_eq!(&encoded, case);
+        #[test]
+        fn failed_when_unmappable_character() {
+            let case = "あい🍣う";
+
+            let result = encode(case, &SaoriCharset::ShiftJIS);
+
+            assert_eq!(
+                result,
+                Err(CharsetError::Unmappable {
+                    position: 2,
+                    character: '🍣'
+                })
+            );
         }
     }
 }
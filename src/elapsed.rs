@@ -0,0 +1,145 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::span::Span;
+
+/// 始期から`date`までの経過日数。始期の日は0
+pub(crate) fn elapsed_days(span: &Span, date: &NaiveDate) -> i64 {
+    (*date - *span.span().start()).num_days()
+}
+
+/// 始期から`date`までの満年数。始期と同じ月日を迎えるごとに1増える
+pub(crate) fn elapsed_years(span: &Span, date: &NaiveDate) -> i32 {
+    let start = span.span().start();
+    let years = date.year() - start.year();
+    if (date.month(), date.day()) < (start.month(), start.day()) {
+        years - 1
+    } else {
+        years
+    }
+}
+
+/// 元号の最後の日。今日まで続いている(終期が`****`の)元号はNoneを返す。
+/// 日付で探すときと同じ終期とするので、時刻付きの終期はその前日(次の元号の始まる日の前日)になる
+pub(crate) fn last_day(span: &Span) -> Option<NaiveDate> {
+    if span.is_ongoing() {
+        return None;
+    }
+    Some(*span.span().end())
+}
+
+/// 元号の期間の日数。始期と最後の日を含み、続いている元号は`today`までとする
+pub(crate) fn span_days(span: &Span, today: &NaiveDate) -> i64 {
+    let last = last_day(span).unwrap_or(*today);
+    (last - *span.span().start()).num_days() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heisei() -> Span {
+        Span::new(
+            "平成".to_string(),
+            NaiveDate::from_ymd_opt(1989, 1, 8).unwrap(),
+            NaiveDate::from_ymd_opt(2019, 4, 30).unwrap(),
+        )
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2023, 10, 1).unwrap()
+    }
+
+    mod elapsed_days {
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            let span = heisei();
+            assert_eq!(
+                elapsed_days(&span, &NaiveDate::from_ymd_opt(1989, 1, 8).unwrap()),
+                0
+            );
+            assert_eq!(
+                elapsed_days(&span, &NaiveDate::from_ymd_opt(1990, 1, 8).unwrap()),
+                365
+            );
+        }
+    }
+
+    mod elapsed_years {
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            let span = heisei();
+            assert_eq!(
+                elapsed_years(&span, &NaiveDate::from_ymd_opt(1989, 12, 31).unwrap()),
+                0
+            );
+            assert_eq!(
+                elapsed_years(&span, &NaiveDate::from_ymd_opt(2019, 1, 7).unwrap()),
+                29
+            );
+            assert_eq!(
+                elapsed_years(&span, &NaiveDate::from_ymd_opt(2019, 1, 8).unwrap()),
+                30
+            );
+        }
+    }
+
+    mod last_day {
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            assert_eq!(last_day(&heisei()), NaiveDate::from_ymd_opt(2019, 4, 30));
+        }
+
+        #[test]
+        fn return_date_only_end_when_end_has_time() {
+            // 日付で探すときと同じく、時刻付きの終期の前日(1926-12-24)になる
+            let contents = "大正,1912-07-30,1926-12-25T01:25\n";
+            let (_, spans) = crate::span::parse_contents(contents).unwrap().unwrap();
+            assert_eq!(last_day(&spans[0]), NaiveDate::from_ymd_opt(1926, 12, 24));
+
+            // 1926-12-25T16:25Zは、日本標準時では12月26日なので、その前日
+            let contents = "大正,1912-07-30,1926-12-25T16:25Z\n";
+            let (_, spans) = crate::span::parse_contents(contents).unwrap().unwrap();
+            assert_eq!(last_day(&spans[0]), NaiveDate::from_ymd_opt(1926, 12, 25));
+        }
+
+        #[test]
+        fn return_none_when_continuing() {
+            let contents = "令和,2019-05-01,****\n";
+            let (_, spans) = crate::span::parse_contents(contents).unwrap().unwrap();
+            assert_eq!(last_day(&spans[0]), None);
+        }
+
+        #[test]
+        fn return_date_when_fixed_end_is_today() {
+            // 終期が今日でも、`****`でなければ続いているとはしない
+            let span = Span::new(
+                "令和".to_string(),
+                NaiveDate::from_ymd_opt(2019, 5, 1).unwrap(),
+                today(),
+            );
+            assert_eq!(last_day(&span), Some(today()));
+        }
+    }
+
+    mod span_days {
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            assert_eq!(span_days(&heisei(), &today()), 11070);
+
+            let span = Span::new(
+                "令和".to_string(),
+                NaiveDate::from_ymd_opt(2019, 5, 1).unwrap(),
+                today(),
+            );
+            assert_eq!(span_days(&span, &today()), 1615);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use chrono::{Datelike, NaiveDate};
+
+/// 0001-01-01(先発グレゴリオ暦)のユリウス通日から1日引いたもの
+const JDN_OFFSET_FROM_CE: i64 = 1721425;
+
+/// ユリウス暦の日付。年は0年(紀元前1年)を含む天文学的な数え方
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct JulianDate {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl JulianDate {
+    pub fn new(year: i32, month: u32, day: u32) -> JulianDate {
+        JulianDate { year, month, day }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// グレゴリオ暦の日付をユリウス暦の日付に変換する
+    pub fn from_gregorian(date: &NaiveDate) -> JulianDate {
+        let jdn = date.num_days_from_ce() as i64 + JDN_OFFSET_FROM_CE;
+
+        let c = jdn + 32082;
+        let d = (4 * c + 3).div_euclid(1461);
+        let e = c - (1461 * d).div_euclid(4);
+        let m = (5 * e + 2) / 153;
+
+        JulianDate {
+            year: (d - 4800 + m / 10) as i32,
+            month: (m + 3 - 12 * (m / 10)) as u32,
+            day: (e - (153 * m + 2) / 5 + 1) as u32,
+        }
+    }
+
+    /// ユリウス暦の日付をグレゴリオ暦の日付に変換する。
+    /// ユリウス暦として存在しない日付のときはNoneを返す。
+    pub fn to_gregorian(&self) -> Option<NaiveDate> {
+        if !(1..=12).contains(&self.month) || self.day < 1 || self.day > self.days_in_month() {
+            return None;
+        }
+
+        let a = (14 - self.month as i64) / 12;
+        let y = self.year as i64 + 4800 - a;
+        let m = self.month as i64 + 12 * a - 3;
+        let jdn = self.day as i64 + (153 * m + 2) / 5 + 365 * y + y.div_euclid(4) - 32083;
+
+        NaiveDate::from_num_days_from_ce_opt(i32::try_from(jdn - JDN_OFFSET_FROM_CE).ok()?)
+    }
+
+    /// その月の日数。4で割り切れる年は全て閏年とする
+    fn days_in_month(&self) -> u32 {
+        match self.month {
+            2 if self.year.rem_euclid(4) == 0 => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod from_gregorian {
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            let cases = [
+                ((1582, 10, 15), JulianDate::new(1582, 10, 5)),
+                ((1582, 10, 14), JulianDate::new(1582, 10, 4)),
+                ((1573, 9, 4), JulianDate::new(1573, 8, 25)),
+                ((2023, 10, 1), JulianDate::new(2023, 9, 18)),
+                ((1, 1, 1), JulianDate::new(1, 1, 3)),
+                ((-100, 3, 1), JulianDate::new(-100, 3, 3)),
+            ];
+            for ((y, m, d), expected) in cases.iter() {
+                let date = NaiveDate::from_ymd_opt(*y, *m, *d).unwrap();
+                assert_eq!(&JulianDate::from_gregorian(&date), expected);
+            }
+        }
+    }
+
+    mod to_gregorian {
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            assert_eq!(
+                JulianDate::new(1582, 10, 4).to_gregorian(),
+                NaiveDate::from_ymd_opt(1582, 10, 14)
+            );
+            // グレゴリオ暦にはない閏日
+            assert_eq!(
+                JulianDate::new(1500, 2, 29).to_gregorian(),
+                NaiveDate::from_ymd_opt(1500, 3, 10)
+            );
+            assert_eq!(
+                JulianDate::new(-100, 3, 3).to_gregorian(),
+                NaiveDate::from_ymd_opt(-100, 3, 1)
+            );
+        }
+
+        #[test]
+        fn return_none_when_invalid_date() {
+            assert_eq!(JulianDate::new(1501, 2, 29).to_gregorian(), None);
+            assert_eq!(JulianDate::new(1582, 13, 1).to_gregorian(), None);
+            assert_eq!(JulianDate::new(1582, 4, 31).to_gregorian(), None);
+            assert_eq!(JulianDate::new(1582, 4, 0).to_gregorian(), None);
+        }
+
+        #[test]
+        fn checking_value_round_trip() {
+            let mut date = NaiveDate::from_ymd_opt(-1, 1, 1).unwrap();
+            let end = NaiveDate::from_ymd_opt(4, 12, 31).unwrap();
+            while date <= end {
+                assert_eq!(JulianDate::from_gregorian(&date).to_gregorian(), Some(date));
+                date = date.succ_opt().unwrap();
+            }
+        }
+    }
+}
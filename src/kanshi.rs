@@ -0,0 +1,62 @@
+use chrono::{Datelike, NaiveDate};
+
+const STEMS: [char; 10] = ['甲', '乙', '丙', '丁', '戊', '己', '庚', '辛', '壬', '癸'];
+const BRANCHES: [char; 12] = [
+    '子', '丑', '寅', '卯', '辰', '巳', '午', '未', '申', '酉', '戌', '亥',
+];
+
+/// 西暦0001-01-01(グレゴリオ暦)の干支の番号(甲子が0)
+const DAY_OFFSET: i64 = 1721474;
+
+/// 年の干支。`year`は暦の年(太陰太陽暦なら旧暦の年)
+pub(crate) fn year_kanshi(year: i32) -> String {
+    kanshi((year as i64 - 4).rem_euclid(60))
+}
+
+/// 日の干支
+pub(crate) fn day_kanshi(date: &NaiveDate) -> String {
+    kanshi((date.num_days_from_ce() as i64 + DAY_OFFSET).rem_euclid(60))
+}
+
+fn kanshi(index: i64) -> String {
+    let index = index as usize;
+    [STEMS[index % 10], BRANCHES[index % 12]].iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod year_kanshi {
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            assert_eq!(year_kanshi(1702), "壬午");
+            assert_eq!(year_kanshi(1984), "甲子");
+            assert_eq!(year_kanshi(2023), "癸卯");
+            assert_eq!(year_kanshi(4), "甲子");
+            assert_eq!(year_kanshi(3), "癸亥");
+        }
+    }
+
+    mod day_kanshi {
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            assert_eq!(
+                day_kanshi(&NaiveDate::from_ymd_opt(2000, 1, 1).unwrap()),
+                "戊午"
+            );
+            assert_eq!(
+                day_kanshi(&NaiveDate::from_ymd_opt(2023, 10, 1).unwrap()),
+                "壬辰"
+            );
+            assert_eq!(
+                day_kanshi(&NaiveDate::from_ymd_opt(2023, 10, 2).unwrap()),
+                "癸巳"
+            );
+        }
+    }
+}
@@ -0,0 +1,326 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::span::{self, CountingRule, NaiveDateSpan, Span};
+use crate::validate::{list_dirs, target_files, IssueKind, ValidationIssue};
+
+/// 読み込みに成功したファイル
+struct LintedFile {
+    file: String,
+    contents: String,
+    span: NaiveDateSpan,
+    spans: Vec<Span>,
+    initial: char,
+}
+
+/// `path`(`gengou_lists`)内のファイル全体を監査し、見つかった問題を全て返す。
+/// 天皇の一覧があれば、それも別に監査する。
+/// 読み込みに失敗するファイルは対象外とする(`validate_spans`で確かめる)。
+pub(crate) fn lint_spans(path: &Path) -> Result<Vec<ValidationIssue>, std::io::Error> {
+    let mut issues = Vec::new();
+    for (prefix, dir) in list_dirs(path) {
+        issues.extend(lint_dir(&dir, &prefix)?);
+    }
+    Ok(issues)
+}
+
+/// ディレクトリ内のファイル全体を監査する。ファイル名には`prefix`を付ける
+fn lint_dir(path: &Path, prefix: &str) -> Result<Vec<ValidationIssue>, std::io::Error> {
+    let mut files = Vec::new();
+    for (file, entry) in target_files(path)? {
+        let contents = if let Ok(c) = fs::read_to_string(&entry) {
+            c
+        } else {
+            continue;
+        };
+        let (span, spans) = if let Ok(Some(r)) = span::parse_contents(&contents) {
+            r
+        } else {
+            continue;
+        };
+        let initial = span::get_initial_char_after_underbar(&file).expect("already checked");
+
+        files.push(LintedFile {
+            file: format!("{}{}", prefix, file),
+            contents,
+            span,
+            spans,
+            initial,
+        });
+    }
+
+    let mut issues = Vec::new();
+    for target in files.iter() {
+        issues.extend(lint_header(&target.file, &target.contents, &target.span));
+        issues.extend(lint_now(&target.file, &target.contents));
+    }
+    issues.extend(lint_duplicate_initial(&files));
+    issues.extend(lint_range_overlap(&files));
+
+    issues.sort_by_key(|v| (v.file().to_string(), v.line(), v.column()));
+    Ok(issues)
+}
+
+/// 先頭のコメント(`// 645-08-01_1329-09-30`)の範囲が、実際の範囲と同じか確かめる
+fn lint_header(file: &str, contents: &str, span: &NaiveDateSpan) -> Option<ValidationIssue> {
+    let (index, line) = contents
+        .lines()
+        .enumerate()
+        .find(|(_, l)| !l.trim().is_empty())?;
+    let comment = line.trim_start().strip_prefix("//")?;
+    let header = comment.trim();
+    let (start_str, end_str) = header.split_once('_')?;
+    // 範囲の書式でなければ、ただのコメントとする
+    let (start, end) = match (
+        span::parse_datetime(start_str),
+        span::parse_datetime(end_str),
+    ) {
+        (Ok(s), Ok(e)) => (s, e),
+        _ => return None,
+    };
+
+    if &start == span.start() && &end == span.end() {
+        return None;
+    }
+
+    let column = line[..line.find(header).expect("header is in line")]
+        .chars()
+        .count()
+        + 1;
+    Some(ValidationIssue::new(
+        file.to_string(),
+        index + 1,
+        column,
+        IssueKind::HeaderMismatch,
+        header.to_string(),
+    ))
+}
+
+/// `****`が最後の行以外で使われていないか確かめる
+fn lint_now(file: &str, contents: &str) -> Vec<ValidationIssue> {
+    let lines: Vec<(usize, Vec<(usize, &str)>)> = contents
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| span::split_line(line).map(|f| (index + 1, f)))
+        .collect();
+
+    let mut issues = Vec::new();
+    let last = lines.len().saturating_sub(1);
+    for (line_number, fields) in lines[..last].iter() {
+        for (column, field) in fields.iter().skip(1).take(2) {
+            if *field == "****" {
+                issues.push(ValidationIssue::new(
+                    file.to_string(),
+                    *line_number,
+                    *column,
+                    IssueKind::MisplacedNow,
+                    field.to_string(),
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+/// 同じ範囲のファイルで頭文字が重複していないか確かめる。
+/// 重複しているときは、ファイル名の順で後ろのファイルで読み込まれるので、前のファイルを問題とする。
+fn lint_duplicate_initial(files: &[LintedFile]) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let mut seen: HashMap<(&NaiveDateSpan, char), &str> = HashMap::new();
+    for target in files.iter() {
+        if let Some(other) = seen.insert((&target.span, target.initial), &target.file) {
+            issues.push(ValidationIssue::new(
+                other.to_string(),
+                0,
+                0,
+                IssueKind::DuplicateInitial(target.file.clone()),
+                target.initial.to_string(),
+            ));
+        }
+    }
+
+    issues
+}
+
+/// 範囲の異なるファイル同士で、範囲が重なっていないか確かめる。
+/// 範囲の同じファイル(北朝・南朝など)は重なっていてよい。
+/// 重なる部分に始まる元号が全て立年改元(`rule=立年改元`、年の初めに遡る改元)なら、
+/// 遡った分の重なりとして問題にしない(明治など)。
+fn lint_range_overlap(files: &[LintedFile]) -> Vec<ValidationIssue> {
+    let mut sorted: Vec<&LintedFile> = files.iter().collect();
+    sorted.sort_by(|a, b| a.span.cmp(&b.span));
+
+    let mut issues = Vec::new();
+    for (i, prev) in sorted.iter().enumerate() {
+        for target in sorted[i + 1..].iter() {
+            if target.span == prev.span {
+                continue;
+            }
+            let is_retroactive = target
+                .spans
+                .iter()
+                .filter(|v| v.span().start() < prev.span.end())
+                .all(|v| v.counting_rule() == CountingRule::Ritsunen);
+            // 境界の日が同じなのは重なりとしない
+            if target.span.start() < prev.span.end() && !is_retroactive {
+                let end = std::cmp::min(prev.span.end(), target.span.end());
+                issues.push(ValidationIssue::new(
+                    target.file.clone(),
+                    0,
+                    0,
+                    IssueKind::RangeOverlap(prev.file.clone()),
+                    format!(
+                        "{}_{}",
+                        target.span.start().format("%Y-%m-%d"),
+                        end.format("%Y-%m-%d")
+                    ),
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn case_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "japanesegengou_lint_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        for (file, contents) in files.iter() {
+            fs::write(path.join(file), contents).unwrap();
+        }
+        path
+    }
+
+    fn lint_to_strings(path: &Path) -> Vec<String> {
+        lint_spans(path)
+            .unwrap()
+            .iter()
+            .map(|v| v.to_string())
+            .collect()
+    }
+
+    mod lint_spans {
+        use super::*;
+
+        #[test]
+        fn success_and_return_empty_when_bundled_lists() {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(span::SPAN_DIR_PATH);
+            let result = lint_spans(&path).unwrap();
+            assert_eq!(result, vec![]);
+        }
+
+        #[test]
+        fn return_nothing_when_overlap_is_retroactive() {
+            let path = case_dir(
+                "retroactive",
+                &[
+                    ("001_a.txt", "a,2000-01-01,2000-12-31\n"),
+                    (
+                        "002_a.txt",
+                        "b,2000-06-01,2001-12-31,,,,rule=立年改元\nc,2001-12-31,2002-12-31\n",
+                    ),
+                    ("003_a.txt", "d,2002-06-01,2003-12-31,,,,rule=yunen\n"),
+                ],
+            );
+
+            // 踰年改元は遡らないので問題とする
+            assert_eq!(
+                lint_to_strings(&path),
+                vec!["003_a.txt:0:0:range-overlap(002_a.txt):2002-06-01_2002-12-31".to_string()]
+            );
+
+            fs::remove_dir_all(&path).unwrap();
+        }
+
+        #[test]
+        fn return_duplicate_initial() {
+            let path = case_dir(
+                "duplicate",
+                &[
+                    (
+                        "001_a.txt",
+                        "// 2000-01-01_2000-12-31\na,2000-01-01,2000-12-31\n",
+                    ),
+                    ("001_alpha.txt", "b,2000-01-01,2000-12-31\n"),
+                    ("001_b.txt", "c,2000-01-01,2000-12-31\n"),
+                ],
+            );
+
+            assert_eq!(
+                lint_to_strings(&path),
+                vec!["001_a.txt:0:0:duplicate-initial(001_alpha.txt):a".to_string()]
+            );
+
+            fs::remove_dir_all(&path).unwrap();
+        }
+
+        #[test]
+        fn return_misplaced_now_and_range_overlap() {
+            let path = case_dir(
+                "now",
+                &[
+                    ("001_a.txt", "a,2000-01-01,****\nb,2000-06-01,2000-12-31\n"),
+                    ("002_a.txt", "c,2000-12-31,2001-12-31\n"),
+                    ("003_a.txt", "d,2001-06-01,2002-12-31\n"),
+                    // 読み込みに失敗するファイルは対象外
+                    ("004_a.txt", "e,2002_01-01,2003-12-31\n"),
+                ],
+            );
+
+            assert_eq!(
+                lint_to_strings(&path),
+                vec![
+                    "001_a.txt:1:14:misplaced-now:****".to_string(),
+                    "003_a.txt:0:0:range-overlap(002_a.txt):2001-06-01_2001-12-31".to_string(),
+                ]
+            );
+
+            fs::remove_dir_all(&path).unwrap();
+        }
+    }
+
+    mod lint_header {
+        use super::*;
+
+        #[test]
+        fn return_none_when_header_is_not_range() {
+            let span = NaiveDateSpan::new(
+                chrono::NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+                chrono::NaiveDate::from_ymd_opt(2000, 12, 31).unwrap(),
+            );
+            assert_eq!(lint_header("001_a.txt", "// comment\n", &span), None);
+            assert_eq!(
+                lint_header("001_a.txt", "a,2000-01-01,2000-12-31\n", &span),
+                None
+            );
+            assert_eq!(
+                lint_header("001_a.txt", "\n//2000-01-01_2000-12-31\n", &span),
+                None
+            );
+        }
+
+        #[test]
+        fn return_issue_when_header_is_different() {
+            let span = NaiveDateSpan::new(
+                chrono::NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+                chrono::NaiveDate::from_ymd_opt(2000, 12, 31).unwrap(),
+            );
+            let result = lint_header("001_a.txt", "\n  //  2000-01-02_2000-12-31\n", &span);
+            assert_eq!(
+                result.map(|v| v.to_string()),
+                Some("001_a.txt:2:7:header-mismatch:2000-01-02_2000-12-31".to_string())
+            );
+        }
+    }
+}
@@ -0,0 +1,484 @@
+use chrono::{Datelike, NaiveDate};
+
+/// グレゴリオ暦に改暦される日付(明治6年1月1日)
+const GREGORIAN_ADOPTION: (i32, u32, u32) = (1873, 1, 1);
+/// 旧暦を計算する範囲の始まり。これより前の日付は扱わない
+const CALCULATION_START: (i32, u32, u32) = (1, 1, 1);
+/// 天保暦(定気法)が施行された日付(天保15年1月1日)
+const TENPO_ADOPTION: (i32, u32, u32) = (1844, 2, 18);
+
+/// 日付を数える時刻の基準(日本標準時、UTC+9)
+const TIMEZONE_OFFSET_DAYS: f64 = 9.0 / 24.0;
+/// 0001-01-01(先発グレゴリオ暦)の00:00(UT)のユリウス日から1日引いたもの
+const JD_OFFSET_FROM_CE: f64 = 1721424.5;
+const SYNODIC_MONTH: f64 = 29.530588861;
+const TROPICAL_YEAR: f64 = 365.242189;
+
+/// 太陰太陽暦(旧暦)の日付
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LunisolarDate {
+    year: i32,
+    month: u32,
+    is_leap: bool,
+    day: u32,
+}
+
+impl LunisolarDate {
+    pub fn new(year: i32, month: u32, is_leap: bool, day: u32) -> LunisolarDate {
+        LunisolarDate {
+            year,
+            month,
+            is_leap,
+            day,
+        }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn is_leap(&self) -> bool {
+        self.is_leap
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// グレゴリオ暦の日付を太陰太陽暦の日付に変換する。
+    /// 改暦(1873-01-01)以後と、計算する範囲(0001-01-01以後)より前の日付はNoneを返す。
+    ///
+    /// 朔と中気は天文計算で求めています(天保暦以前は平気法)。
+    /// 当時の暦法そのものではないため、史料上の暦日と1日程度ずれることがあります。
+    pub fn from_gregorian(date: &NaiveDate) -> Option<LunisolarDate> {
+        let (y, m, d) = GREGORIAN_ADOPTION;
+        if *date >= NaiveDate::from_ymd_opt(y, m, d)? {
+            return None;
+        }
+        let (y, m, d) = CALCULATION_START;
+        if *date < NaiveDate::from_ymd_opt(y, m, d)? {
+            return None;
+        }
+
+        let target = date.num_days_from_ce();
+
+        // 対象日を含む「歳」(冬至を含む月から次の冬至を含む月の前まで)を求める
+        let mut solstice_year = date.year() - 1;
+        let mut month11 = month11_index(solstice_year)?;
+        let mut next_month11 = month11_index(solstice_year + 1)?;
+        if new_moon_day(next_month11) <= target {
+            solstice_year += 1;
+            month11 = next_month11;
+            next_month11 = month11_index(solstice_year + 1)?;
+        }
+
+        let month_index = new_moon_index_on_or_before(target);
+        let offset = month_index - month11;
+
+        // 13ヶ月ある歳では、最初の中気のない月が閏月
+        let mut leap_offset = None;
+        if next_month11 - month11 == 13 {
+            for i in 1..13 {
+                if !has_chuuki(month11 + i)? {
+                    leap_offset = Some(i);
+                    break;
+                }
+            }
+        }
+
+        let (offset, is_leap) = match leap_offset {
+            Some(l) if offset >= l => (offset - 1, offset == l),
+            _ => (offset, false),
+        };
+
+        let month = ((10 + offset) % 12 + 1) as u32;
+        let year = if offset >= 2 {
+            solstice_year + 1
+        } else {
+            solstice_year
+        };
+        let day = (target - new_moon_day(month_index) + 1) as u32;
+
+        Some(LunisolarDate::new(year, month, is_leap, day))
+    }
+
+    /// 太陰太陽暦の日付をグレゴリオ暦の日付に変換する。
+    /// 存在しない日付や、改暦(1873-01-01)以後になる日付はNoneを返す。
+    pub fn to_gregorian(&self) -> Option<NaiveDate> {
+        if self.day == 0 {
+            return None;
+        }
+
+        // 旧暦の月の始まりは、同じ数字のグレゴリオ暦の月の前後2ヶ月程度に収まる
+        let base = NaiveDate::from_ymd_opt(self.year, self.month, 1)?.num_days_from_ce();
+        let first_index = new_moon_index_on_or_before(base) - 1;
+
+        for k in first_index..first_index + 5 {
+            let start = new_moon_day(k);
+            let start_date = NaiveDate::from_num_days_from_ce_opt(start)?;
+            let target = if let Some(v) = LunisolarDate::from_gregorian(&start_date) {
+                v
+            } else {
+                continue;
+            };
+
+            if target.year == self.year
+                && target.month == self.month
+                && target.is_leap == self.is_leap
+            {
+                let day = start + self.day as i32 - 1;
+                if day >= new_moon_day(k + 1) {
+                    return None;
+                }
+                return NaiveDate::from_num_days_from_ce_opt(day)
+                    .filter(|v| LunisolarDate::from_gregorian(v).is_some());
+            }
+        }
+
+        None
+    }
+}
+
+/// 冬至を含む月(十一月)の朔の番号
+fn month11_index(year: i32) -> Option<i32> {
+    let solstice = winter_solstice_jd(year)?;
+    Some(new_moon_index_on_or_before(jd_to_local_day(solstice)))
+}
+
+/// 指定日(0001-01-01を1とする通日)以前で最も近い朔の番号
+fn new_moon_index_on_or_before(day: i32) -> i32 {
+    let jd = day as f64 + JD_OFFSET_FROM_CE;
+    let mut k = ((jd - 2451550.1) / SYNODIC_MONTH).floor() as i32;
+    while new_moon_day(k) > day {
+        k -= 1;
+    }
+    while new_moon_day(k + 1) <= day {
+        k += 1;
+    }
+    k
+}
+
+/// 朔の番号からその朔の日(0001-01-01を1とする通日)を求める
+fn new_moon_day(k: i32) -> i32 {
+    let jde = new_moon_jde(k as f64);
+    jd_to_local_day(jde - delta_t_days(jde))
+}
+
+/// 番号`k`の月が中気を含むか
+fn has_chuuki(k: i32) -> Option<bool> {
+    let start = new_moon_day(k);
+    let end = new_moon_day(k + 1);
+
+    let (y, m, d) = TENPO_ADOPTION;
+    let tenpo = NaiveDate::from_ymd_opt(y, m, d)?.num_days_from_ce();
+
+    let longitude: fn(i32) -> Option<f64> = if start >= tenpo {
+        true_longitude_at_day
+    } else {
+        mean_longitude_at_day
+    };
+
+    Some((longitude(start)? / 30.0).floor() != (longitude(end)? / 30.0).floor())
+}
+
+/// 定気: 指定日の始まりの時点での太陽黄経
+fn true_longitude_at_day(day: i32) -> Option<f64> {
+    let jd = local_day_start_jd(day);
+    Some(solar_longitude(jd + delta_t_days(jd)))
+}
+
+/// 平気: 冬至から一年を等分した黄経
+fn mean_longitude_at_day(day: i32) -> Option<f64> {
+    let jd = local_day_start_jd(day);
+    let date = NaiveDate::from_num_days_from_ce_opt(day)?;
+    let mut solstice = winter_solstice_jd(date.year())?;
+    if solstice > jd {
+        solstice = winter_solstice_jd(date.year() - 1)?;
+    }
+
+    Some(270.0 + 360.0 * (jd - solstice) / TROPICAL_YEAR)
+}
+
+/// 指定年の冬至の時刻(ユリウス日、UT)
+fn winter_solstice_jd(year: i32) -> Option<f64> {
+    let day = NaiveDate::from_ymd_opt(year, 12, 21)?.num_days_from_ce();
+    let mut jd = day as f64 + JD_OFFSET_FROM_CE;
+
+    for _ in 0..8 {
+        let diff = normalize_degree(270.0 - solar_longitude(jd + delta_t_days(jd)) + 180.0) - 180.0;
+        jd += diff * TROPICAL_YEAR / 360.0;
+    }
+
+    Some(jd)
+}
+
+fn local_day_start_jd(day: i32) -> f64 {
+    day as f64 + JD_OFFSET_FROM_CE - TIMEZONE_OFFSET_DAYS
+}
+
+fn jd_to_local_day(jd: f64) -> i32 {
+    (jd - JD_OFFSET_FROM_CE + TIMEZONE_OFFSET_DAYS).floor() as i32
+}
+
+fn normalize_degree(v: f64) -> f64 {
+    v.rem_euclid(360.0)
+}
+
+/// 太陽の視黄経(度)
+/// Jean Meeus "Astronomical Algorithms" 第25章の略算式
+fn solar_longitude(jde: f64) -> f64 {
+    let t = (jde - 2451545.0) / 36525.0;
+    let l0 = 280.46646 + 36000.76983 * t + 0.0003032 * t * t;
+    let m = (357.52911 + 35999.05029 * t - 0.0001537 * t * t).to_radians();
+    let c = (1.914602 - 0.004817 * t - 0.000014 * t * t) * m.sin()
+        + (0.019993 - 0.000101 * t) * (2.0 * m).sin()
+        + 0.000289 * (3.0 * m).sin();
+    let omega = (125.04 - 1934.136 * t).to_radians();
+
+    normalize_degree(l0 + c - 0.00569 - 0.00478 * omega.sin())
+}
+
+/// 番号`k`の朔の時刻(ユリウス日、TT)
+/// Jean Meeus "Astronomical Algorithms" 第49章
+fn new_moon_jde(k: f64) -> f64 {
+    let t = k / 1236.85;
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let t4 = t3 * t;
+
+    let jde =
+        2451550.09766 + 29.530588861 * k + 0.00015437 * t2 - 0.000000150 * t3 + 0.00000000073 * t4;
+
+    let e = 1.0 - 0.002516 * t - 0.0000074 * t2;
+    let m = (2.5534 + 29.10535670 * k - 0.0000014 * t2 - 0.00000011 * t3).to_radians();
+    let mp = (201.5643 + 385.81693528 * k + 0.0107582 * t2 + 0.00001238 * t3 - 0.000000058 * t4)
+        .to_radians();
+    let f = (160.7108 + 390.67050284 * k - 0.0016118 * t2 - 0.00000227 * t3 + 0.000000011 * t4)
+        .to_radians();
+    let omega = (124.7746 - 1.56375588 * k + 0.0020672 * t2 + 0.00000215 * t3).to_radians();
+
+    let correction = -0.40720 * mp.sin()
+        + 0.17241 * e * m.sin()
+        + 0.01608 * (2.0 * mp).sin()
+        + 0.01039 * (2.0 * f).sin()
+        + 0.00739 * e * (mp - m).sin()
+        - 0.00514 * e * (mp + m).sin()
+        + 0.00208 * e * e * (2.0 * m).sin()
+        - 0.00111 * (mp - 2.0 * f).sin()
+        - 0.00057 * (mp + 2.0 * f).sin()
+        + 0.00056 * e * (2.0 * mp + m).sin()
+        - 0.00042 * (3.0 * mp).sin()
+        + 0.00042 * e * (m + 2.0 * f).sin()
+        + 0.00038 * e * (m - 2.0 * f).sin()
+        - 0.00024 * e * (2.0 * mp - m).sin()
+        - 0.00017 * omega.sin()
+        - 0.00007 * (mp + 2.0 * m).sin()
+        + 0.00004 * (2.0 * mp - 2.0 * f).sin()
+        + 0.00004 * (3.0 * m).sin()
+        + 0.00003 * (mp + m - 2.0 * f).sin()
+        + 0.00003 * (2.0 * mp + 2.0 * f).sin()
+        - 0.00003 * (mp + m + 2.0 * f).sin()
+        + 0.00003 * (mp - m + 2.0 * f).sin()
+        - 0.00002 * (mp - m - 2.0 * f).sin()
+        - 0.00002 * (3.0 * mp + m).sin()
+        + 0.00002 * (4.0 * mp).sin();
+
+    let additional: [(f64, f64, f64); 14] = [
+        (0.000325, 299.77, 0.107408),
+        (0.000165, 251.88, 0.016321),
+        (0.000164, 251.83, 26.651886),
+        (0.000126, 349.42, 36.412478),
+        (0.000110, 84.66, 18.206239),
+        (0.000062, 141.74, 53.303771),
+        (0.000060, 207.14, 2.453732),
+        (0.000056, 154.84, 7.306860),
+        (0.000047, 34.52, 27.261239),
+        (0.000042, 207.19, 0.121824),
+        (0.000040, 291.34, 1.844379),
+        (0.000037, 161.72, 24.198154),
+        (0.000035, 239.56, 25.513099),
+        (0.000023, 331.55, 3.592518),
+    ];
+    let additional: f64 = additional
+        .iter()
+        .enumerate()
+        .map(|(i, (c, a, b))| {
+            let mut angle = a + b * k;
+            if i == 0 {
+                angle -= 0.009173 * t2;
+            }
+            c * angle.to_radians().sin()
+        })
+        .sum();
+
+    jde + correction + additional
+}
+
+/// 地球時と世界時の差(日)
+/// Espenak & Meeus による多項式近似
+fn delta_t_days(jd: f64) -> f64 {
+    let y = 2000.0 + (jd - 2451545.0) / 365.25;
+
+    let seconds = if y < 500.0 {
+        let u = y / 100.0;
+        10583.6 - 1014.41 * u + 33.78311 * u.powi(2) - 5.952053 * u.powi(3) - 0.1798452 * u.powi(4)
+            + 0.022174192 * u.powi(5)
+            + 0.0090316521 * u.powi(6)
+    } else if y < 1600.0 {
+        let u = (y - 1000.0) / 100.0;
+        1574.2 - 556.01 * u + 71.23472 * u.powi(2) + 0.319781 * u.powi(3)
+            - 0.8503463 * u.powi(4)
+            - 0.005050998 * u.powi(5)
+            + 0.0083572073 * u.powi(6)
+    } else if y < 1700.0 {
+        let t = y - 1600.0;
+        120.0 - 0.9808 * t - 0.01532 * t.powi(2) + t.powi(3) / 7129.0
+    } else if y < 1800.0 {
+        let t = y - 1700.0;
+        8.83 + 0.1603 * t - 0.0059285 * t.powi(2) + 0.00013336 * t.powi(3) - t.powi(4) / 1174000.0
+    } else if y < 1860.0 {
+        let t = y - 1800.0;
+        13.72 - 0.332447 * t + 0.0068612 * t.powi(2) + 0.0041116 * t.powi(3)
+            - 0.00037436 * t.powi(4)
+            + 0.0000121272 * t.powi(5)
+            - 0.0000001699 * t.powi(6)
+            + 0.000000000875 * t.powi(7)
+    } else if y < 1900.0 {
+        let t = y - 1860.0;
+        7.62 + 0.5737 * t - 0.251754 * t.powi(2) + 0.01680668 * t.powi(3) - 0.0004473624 * t.powi(4)
+            + t.powi(5) / 233174.0
+    } else {
+        let t = y - 1900.0;
+        -2.79 + 1.494119 * t - 0.0598939 * t.powi(2) + 0.0061966 * t.powi(3) - 0.000197 * t.powi(4)
+    };
+
+    seconds / 86400.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod from_gregorian {
+        use super::*;
+
+        #[test]
+        fn checking_value_normal() {
+            let case = NaiveDate::from_ymd_opt(1703, 1, 30).unwrap();
+            assert_eq!(
+                LunisolarDate::from_gregorian(&case),
+                Some(LunisolarDate::new(1702, 12, false, 14))
+            );
+
+            let case = NaiveDate::from_ymd_opt(1582, 7, 1).unwrap();
+            assert_eq!(
+                LunisolarDate::from_gregorian(&case),
+                Some(LunisolarDate::new(1582, 6, false, 2))
+            );
+
+            let case = NaiveDate::from_ymd_opt(1868, 1, 25).unwrap();
+            assert_eq!(
+                LunisolarDate::from_gregorian(&case),
+                Some(LunisolarDate::new(1868, 1, false, 1))
+            );
+        }
+
+        #[test]
+        fn checking_value_leap_month() {
+            let case = NaiveDate::from_ymd_opt(1843, 11, 1).unwrap();
+            assert_eq!(
+                LunisolarDate::from_gregorian(&case),
+                Some(LunisolarDate::new(1843, 9, true, 10))
+            );
+
+            let case = NaiveDate::from_ymd_opt(1862, 10, 1).unwrap();
+            assert_eq!(
+                LunisolarDate::from_gregorian(&case),
+                Some(LunisolarDate::new(1862, 8, true, 8))
+            );
+        }
+
+        #[test]
+        fn checking_value_border_of_tenpo_calendar() {
+            let case = NaiveDate::from_ymd_opt(1844, 2, 17).unwrap();
+            assert_eq!(
+                LunisolarDate::from_gregorian(&case),
+                Some(LunisolarDate::new(1843, 12, false, 29))
+            );
+
+            let case = NaiveDate::from_ymd_opt(1844, 2, 18).unwrap();
+            assert_eq!(
+                LunisolarDate::from_gregorian(&case),
+                Some(LunisolarDate::new(1844, 1, false, 1))
+            );
+        }
+
+        #[test]
+        fn checking_value_border_of_gregorian_adoption() {
+            let case = NaiveDate::from_ymd_opt(1872, 12, 31).unwrap();
+            assert_eq!(
+                LunisolarDate::from_gregorian(&case),
+                Some(LunisolarDate::new(1872, 12, false, 2))
+            );
+
+            let case = NaiveDate::from_ymd_opt(1873, 1, 1).unwrap();
+            assert_eq!(LunisolarDate::from_gregorian(&case), None);
+        }
+
+        #[test]
+        fn return_none_when_before_calculation_start() {
+            let case = NaiveDate::from_ymd_opt(1, 1, 1).unwrap();
+            assert!(LunisolarDate::from_gregorian(&case).is_some());
+
+            let case = NaiveDate::from_ymd_opt(0, 12, 31).unwrap();
+            assert_eq!(LunisolarDate::from_gregorian(&case), None);
+            assert_eq!(LunisolarDate::from_gregorian(&NaiveDate::MIN), None);
+        }
+    }
+    mod to_gregorian {
+        use super::*;
+
+        #[test]
+        fn checking_value_normal() {
+            let case = LunisolarDate::new(1702, 12, false, 14);
+            assert_eq!(case.to_gregorian(), NaiveDate::from_ymd_opt(1703, 1, 30));
+
+            let case = LunisolarDate::new(1582, 6, false, 2);
+            assert_eq!(case.to_gregorian(), NaiveDate::from_ymd_opt(1582, 7, 1));
+        }
+
+        #[test]
+        fn checking_value_leap_month() {
+            let case = LunisolarDate::new(1865, 5, true, 9);
+            assert_eq!(case.to_gregorian(), NaiveDate::from_ymd_opt(1865, 7, 1));
+
+            let case = LunisolarDate::new(1865, 5, false, 9);
+            assert_eq!(case.to_gregorian(), NaiveDate::from_ymd_opt(1865, 6, 2));
+        }
+
+        #[test]
+        fn return_none_when_leap_month_does_not_exist() {
+            let case = LunisolarDate::new(1702, 12, true, 14);
+            assert_eq!(case.to_gregorian(), None);
+        }
+
+        #[test]
+        fn return_none_when_day_is_out_of_month() {
+            let case = LunisolarDate::new(1702, 12, false, 31);
+            assert_eq!(case.to_gregorian(), None);
+
+            let case = LunisolarDate::new(1702, 12, false, 0);
+            assert_eq!(case.to_gregorian(), None);
+        }
+
+        #[test]
+        fn return_none_when_after_gregorian_adoption() {
+            let case = LunisolarDate::new(1872, 12, false, 3);
+            assert_eq!(case.to_gregorian(), None);
+        }
+    }
+}
@@ -0,0 +1,600 @@
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use japanesegengou::procedure;
+use japanesegengou::request::{SaoriCommand, SaoriRequest};
+use japanesegengou::response::SaoriResponse;
+
+const USAGE: &str = "\
+Usage:
+    japanesegengou <DATE> [--mode <MODE>] [--select <SELECTOR>] [--format <PATTERN>] [--time <TIME>] [--lists <DIR>]
+    japanesegengou --reverse <GENGOU_DATE> [--mode <MODE>] [--select <SELECTOR>] [--lists <DIR>]
+    japanesegengou <DATE> --range <DATE> [--select <SELECTOR>] [--lists <DIR>]
+    japanesegengou --list <files|eras> [--select <SELECTOR>] [--lists <DIR>]
+    japanesegengou --lookup <NAME> [--select <SELECTOR>] [--lists <DIR>]
+    japanesegengou --previous <GENGOU> [--select <SELECTOR>] [--lists <DIR>]
+    japanesegengou --next <GENGOU> [--select <SELECTOR>] [--lists <DIR>]
+    japanesegengou --validate [--lists <DIR>]
+    japanesegengou --lint [--lists <DIR>]
+
+DATE:
+    2023-10-01、2023/10/01、20231001、@を付けたUnix時間(秒、@1696086000)、today のいずれか
+
+Options:
+    -m, --mode <MODE>           動作指定パラメータ(例: Gk!)
+    -s, --select <SELECTOR>     ファイル指定パラメータ(例: *_h)
+    -f, --format <PATTERN>      出力の書式(例: %g%Y.%m.%d)
+    -t, --time <TIME>           時刻(例: 01:00、01:00+09:00)
+    -l, --lists <DIR>           gengou_listsディレクトリ(既定値: ./gengou_lists)
+    -r, --reverse               元号を使用した日付から西暦の日付を割り出す
+        --range <DATE>          DATEまでの期間と重なる元号を全て探す
+        --list <files|eras>     読み込んだファイルまたは元号の一覧を表示する
+        --lookup <NAME>         名前(読み・異表記を含む)から元号を探す
+        --previous <GENGOU>     前の元号を探す
+        --next <GENGOU>         次の元号を探す
+        --validate              gengou_lists内のファイルを検証する
+        --lint                  gengou_lists内のファイル全体を監査する
+    -h, --help                  この説明を表示する
+    -V, --version               バージョンを表示する";
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    target: String,
+    mode: Option<String>,
+    selector: Option<String>,
+    format: Option<String>,
+    time: Option<String>,
+    lists: PathBuf,
+    is_reverse: bool,
+    /// 期間で探すときの終わりの日付
+    range_end: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run(Options),
+    /// `validate`、`lint`のように、gengou_listsのみを対象とする処理
+    Check(&'static str, PathBuf),
+    /// `list`、`lookup`、`previous`、`next`のように、一つの値とファイル指定パラメータを取る処理
+    /// (処理の名前、値、ファイル指定パラメータ、gengou_lists)
+    Query(&'static str, String, Option<String>, PathBuf),
+    Help,
+    Version,
+}
+
+fn main() -> ExitCode {
+    let command = match parse_args(std::env::args().skip(1)) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let is_check = matches!(command, Command::Check(_, _));
+    let (lists, argument) = match command {
+        Command::Run(o) => {
+            let argument = to_argument(&o);
+            (o.lists, argument)
+        }
+        Command::Check(name, lists) => (lists, vec![name.to_string()]),
+        Command::Query(name, value, selector, lists) => {
+            let mut argument = vec![name.to_string(), value];
+            argument.extend(selector);
+            (lists, argument)
+        }
+        Command::Help => {
+            return print_lines(&[USAGE.to_string()])
+                .err()
+                .unwrap_or(ExitCode::SUCCESS);
+        }
+        Command::Version => {
+            return print_lines(&[env!("CARGO_PKG_VERSION").to_string()])
+                .err()
+                .unwrap_or(ExitCode::SUCCESS);
+        }
+    };
+
+    let request = SaoriRequest::new(SaoriCommand::Execute, argument);
+    let mut response = SaoriResponse::from_request(&request);
+    procedure::execute_with_lists(&lists, &request, &mut response);
+
+    if let Err(code) = print_lines(&to_lines(&response)) {
+        return code;
+    }
+
+    // 検証で問題が見つかったときも失敗とする
+    if response.result().starts_with("Error") || (is_check && response.result() != "0") {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn parse_args<I>(args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut target = None;
+    let mut mode = None;
+    let mut selector = None;
+    let mut format = None;
+    let mut time = None;
+    let mut lists = PathBuf::from("gengou_lists");
+    let mut is_reverse = false;
+    let mut range_end = None;
+    let mut query = None;
+    let mut check = None;
+
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "-r" | "--reverse" => is_reverse = true,
+            "--validate" | "--lint" => {
+                let name = if arg == "--lint" { "lint" } else { "validate" };
+                if check.replace(name).is_some() {
+                    return Err("'--validate' and '--lint' cannot be used together.".to_string());
+                }
+            }
+            "-m" | "--mode" => mode = Some(next_value(&mut args, &arg)?),
+            "-s" | "--select" => selector = Some(next_value(&mut args, &arg)?),
+            "-f" | "--format" => format = Some(next_value(&mut args, &arg)?),
+            "-t" | "--time" => time = Some(next_value(&mut args, &arg)?),
+            "--range" => range_end = Some(next_value(&mut args, &arg)?),
+            "--list" | "--lookup" | "--previous" | "--next" => {
+                let name = match arg.as_str() {
+                    "--lookup" => "lookup",
+                    "--previous" => "previous",
+                    "--next" => "next",
+                    _ => "list",
+                };
+                let value = next_value(&mut args, &arg)?;
+                if query.replace((name, value)).is_some() {
+                    return Err(
+                        "'--list', '--lookup', '--previous' and '--next' cannot be used together."
+                            .to_string(),
+                    );
+                }
+            }
+            "-l" | "--lists" => lists = PathBuf::from(next_value(&mut args, &arg)?),
+            _ if arg.starts_with('-') && arg.len() > 1 => {
+                return Err(format!("unknown option '{}'.", arg));
+            }
+            _ => {
+                if target.replace(arg).is_some() {
+                    return Err("too many arguments.".to_string());
+                }
+            }
+        }
+    }
+
+    if let Some((name, value)) = query {
+        if target.is_some() || check.is_some() {
+            return Err("too many arguments.".to_string());
+        }
+        return Ok(Command::Query(name, value, selector, lists));
+    }
+
+    if let Some(name) = check {
+        if target.is_some() {
+            return Err("too many arguments.".to_string());
+        }
+        return Ok(Command::Check(name, lists));
+    }
+
+    let target = target.ok_or_else(|| "target date is required.".to_string())?;
+    if is_reverse && format.is_some() {
+        return Err("'--format' cannot be used with '--reverse'.".to_string());
+    }
+    if is_reverse && time.is_some() {
+        return Err("'--time' cannot be used with '--reverse'.".to_string());
+    }
+    if range_end.is_some() {
+        let unusable = [
+            ("--reverse", is_reverse),
+            ("--mode", mode.is_some()),
+            ("--format", format.is_some()),
+            ("--time", time.is_some()),
+        ];
+        if let Some((name, _)) = unusable.iter().find(|(_, v)| *v) {
+            return Err(format!("'{}' cannot be used with '--range'.", name));
+        }
+    }
+
+    Ok(Command::Run(Options {
+        target,
+        mode,
+        selector,
+        format,
+        time,
+        lists,
+        is_reverse,
+        range_end,
+    }))
+}
+
+fn next_value<I>(args: &mut I, name: &str) -> Result<String, String>
+where
+    I: Iterator<Item = String>,
+{
+    args.next()
+        .ok_or_else(|| format!("option '{}' requires a value.", name))
+}
+
+/// SAORIのArgumentに相当する引数の一覧にする
+fn to_argument(options: &Options) -> Vec<String> {
+    // 期間で探すときは、終わりの日付の後にファイル指定パラメータのみを渡す
+    if let Some(end) = &options.range_end {
+        let mut argument = vec!["range".to_string(), options.target.clone(), end.clone()];
+        argument.extend(options.selector.iter().cloned());
+        return argument;
+    }
+
+    // 日付はそのまま渡し、形式の判別はprocedureに任せる
+    let mut argument = if options.is_reverse {
+        vec!["reverse".to_string(), options.target.clone()]
+    } else {
+        vec![options.target.clone()]
+    };
+
+    // 指定された最後の引数までを、省略されたものは空にして並べる
+    let optional = [
+        &options.mode,
+        &options.selector,
+        &options.format,
+        &options.time,
+    ];
+    let count = optional
+        .iter()
+        .rposition(|v| v.is_some())
+        .map_or(0, |p| p + 1);
+    for value in optional[..count].iter() {
+        argument.push(value.as_ref().cloned().unwrap_or_default());
+    }
+
+    argument
+}
+
+fn to_lines(response: &SaoriResponse) -> Vec<String> {
+    let mut lines = Vec::new();
+    if !response.result().is_empty() {
+        lines.push(format!("Result: {}", response.result()));
+    }
+    for (index, value) in response.value().iter().enumerate() {
+        lines.push(format!("Value{}: {}", index, value));
+    }
+    lines
+}
+
+/// 標準出力へ書き出す。出力先のパイプが閉じられた(`| head -1`など)ときは、何も言わずに書き出すのをやめる
+fn print_lines(lines: &[String]) -> Result<(), ExitCode> {
+    match write_lines(&mut std::io::stdout().lock(), lines) {
+        Err(e) if e.kind() != ErrorKind::BrokenPipe => {
+            eprintln!("Error: {}", e);
+            Err(ExitCode::FAILURE)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn write_lines<W: Write>(out: &mut W, lines: &[String]) -> std::io::Result<()> {
+    for line in lines {
+        writeln!(out, "{}", line)?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(s: &[&str]) -> std::vec::IntoIter<String> {
+        s.iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>()
+            .into_iter()
+    }
+
+    mod parse_args {
+        use super::*;
+
+        #[test]
+        fn success_when_valid_args() {
+            let result = parse_args(to_args(&[
+                "1350-01-01",
+                "--mode",
+                "Gk!",
+                "--select",
+                "*_h",
+                "--lists",
+                "path/to/lists",
+            ]));
+            assert_eq!(
+                result,
+                Ok(Command::Run(Options {
+                    target: "1350-01-01".to_string(),
+                    mode: Some("Gk!".to_string()),
+                    selector: Some("*_h".to_string()),
+                    format: None,
+                    time: None,
+                    lists: PathBuf::from("path/to/lists"),
+                    is_reverse: false,
+                    range_end: None,
+                }))
+            );
+        }
+
+        #[test]
+        fn success_when_reverse() {
+            let result = parse_args(to_args(&["-r", "令和5年10月1日"]));
+            assert_eq!(
+                result,
+                Ok(Command::Run(Options {
+                    target: "令和5年10月1日".to_string(),
+                    mode: None,
+                    selector: None,
+                    format: None,
+                    time: None,
+                    lists: PathBuf::from("gengou_lists"),
+                    is_reverse: true,
+                    range_end: None,
+                }))
+            );
+        }
+
+        #[test]
+        fn success_when_range() {
+            let result = parse_args(to_args(&["1329-09-30", "--range", "1394-08-10", "-s", "h"]));
+            assert_eq!(
+                result,
+                Ok(Command::Run(Options {
+                    target: "1329-09-30".to_string(),
+                    mode: None,
+                    selector: Some("h".to_string()),
+                    format: None,
+                    time: None,
+                    lists: PathBuf::from("gengou_lists"),
+                    is_reverse: false,
+                    range_end: Some("1394-08-10".to_string()),
+                }))
+            );
+            assert!(parse_args(to_args(&["1329-09-30", "--range", "1394-08-10", "-r"])).is_err());
+            assert!(
+                parse_args(to_args(&["1329-09-30", "--range", "1394-08-10", "-m", "I"])).is_err()
+            );
+        }
+
+        #[test]
+        fn success_when_validate_or_lint() {
+            let result = parse_args(to_args(&["--validate", "-l", "path/to/lists"]));
+            assert_eq!(
+                result,
+                Ok(Command::Check("validate", PathBuf::from("path/to/lists")))
+            );
+            assert_eq!(
+                parse_args(to_args(&["--lint"])),
+                Ok(Command::Check("lint", PathBuf::from("gengou_lists")))
+            );
+            assert!(parse_args(to_args(&["--validate", "1350-01-01"])).is_err());
+            assert!(parse_args(to_args(&["--validate", "--lint"])).is_err());
+        }
+
+        #[test]
+        fn success_when_query() {
+            let result = parse_args(to_args(&["--list", "eras", "-s", "h"]));
+            assert_eq!(
+                result,
+                Ok(Command::Query(
+                    "list",
+                    "eras".to_string(),
+                    Some("h".to_string()),
+                    PathBuf::from("gengou_lists")
+                ))
+            );
+            assert!(parse_args(to_args(&["--list"])).is_err());
+            assert!(parse_args(to_args(&["--list", "files", "1350-01-01"])).is_err());
+            assert!(parse_args(to_args(&["--list", "files", "--lint"])).is_err());
+            assert_eq!(
+                parse_args(to_args(&["--lookup", "めいじ"])),
+                Ok(Command::Query(
+                    "lookup",
+                    "めいじ".to_string(),
+                    None,
+                    PathBuf::from("gengou_lists")
+                ))
+            );
+            assert!(parse_args(to_args(&["--list", "files", "--lookup", "明治"])).is_err());
+            assert_eq!(
+                parse_args(to_args(&["--previous", "元禄", "-s", "*_h"])),
+                Ok(Command::Query(
+                    "previous",
+                    "元禄".to_string(),
+                    Some("*_h".to_string()),
+                    PathBuf::from("gengou_lists")
+                ))
+            );
+            assert!(parse_args(to_args(&["--previous", "元禄", "--next", "宝永"])).is_err());
+        }
+
+        #[test]
+        fn success_when_help_or_version() {
+            assert_eq!(parse_args(to_args(&["--help"])), Ok(Command::Help));
+            assert_eq!(parse_args(to_args(&["-V"])), Ok(Command::Version));
+        }
+
+        #[test]
+        fn failed_when_invalid_args() {
+            assert!(parse_args(to_args(&[])).is_err());
+            assert!(parse_args(to_args(&["1350-01-01", "--mode"])).is_err());
+            assert!(parse_args(to_args(&["1350-01-01", "--unknown"])).is_err());
+            assert!(parse_args(to_args(&["1350-01-01", "1351-01-01"])).is_err());
+            assert!(parse_args(to_args(&["-r", "令和5年10月1日", "-f", "%G"])).is_err());
+            assert!(parse_args(to_args(&["-r", "令和5年10月1日", "-t", "01:00"])).is_err());
+        }
+    }
+
+    mod to_argument {
+        use super::*;
+
+        #[test]
+        fn checking_value_normal() {
+            let options = Options {
+                target: "1350-01-01".to_string(),
+                mode: None,
+                selector: Some("*_h".to_string()),
+                format: None,
+                time: None,
+                lists: PathBuf::from("gengou_lists"),
+                is_reverse: false,
+                range_end: None,
+            };
+            assert_eq!(
+                to_argument(&options),
+                vec!["1350-01-01".to_string(), "".to_string(), "*_h".to_string()]
+            );
+        }
+
+        #[test]
+        fn checking_value_format() {
+            let options = Options {
+                target: "2019-04-30".to_string(),
+                mode: None,
+                selector: None,
+                format: Some("%g%Y.%m.%d".to_string()),
+                time: None,
+                lists: PathBuf::from("gengou_lists"),
+                is_reverse: false,
+                range_end: None,
+            };
+            assert_eq!(
+                to_argument(&options),
+                vec![
+                    "2019-04-30".to_string(),
+                    "".to_string(),
+                    "".to_string(),
+                    "%g%Y.%m.%d".to_string(),
+                ]
+            );
+        }
+
+        #[test]
+        fn checking_value_time() {
+            let options = Options {
+                target: "1926-12-25".to_string(),
+                mode: None,
+                selector: None,
+                format: None,
+                time: Some("01:00".to_string()),
+                lists: PathBuf::from("gengou_lists"),
+                is_reverse: false,
+                range_end: None,
+            };
+            assert_eq!(
+                to_argument(&options),
+                vec![
+                    "1926-12-25".to_string(),
+                    "".to_string(),
+                    "".to_string(),
+                    "".to_string(),
+                    "01:00".to_string(),
+                ]
+            );
+        }
+
+        #[test]
+        fn checking_value_reverse() {
+            let options = Options {
+                target: "令和5年10月1日".to_string(),
+                mode: Some("I".to_string()),
+                selector: None,
+                format: None,
+                time: None,
+                lists: PathBuf::from("gengou_lists"),
+                is_reverse: true,
+                range_end: None,
+            };
+            assert_eq!(
+                to_argument(&options),
+                vec![
+                    "reverse".to_string(),
+                    "令和5年10月1日".to_string(),
+                    "I".to_string(),
+                ]
+            );
+        }
+
+        #[test]
+        fn checking_value_range() {
+            let options = Options {
+                target: "1329-09-30".to_string(),
+                mode: None,
+                selector: Some("h".to_string()),
+                format: None,
+                time: None,
+                lists: PathBuf::from("gengou_lists"),
+                is_reverse: false,
+                range_end: Some("1394-08-10".to_string()),
+            };
+            assert_eq!(
+                to_argument(&options),
+                vec![
+                    "range".to_string(),
+                    "1329-09-30".to_string(),
+                    "1394-08-10".to_string(),
+                    "h".to_string(),
+                ]
+            );
+        }
+
+        #[test]
+        fn checking_value_other_date_format() {
+            // 形式の判別はprocedureで行う
+            let options = Options {
+                target: "1350/01/01".to_string(),
+                mode: None,
+                selector: None,
+                format: None,
+                time: None,
+                lists: PathBuf::from("gengou_lists"),
+                is_reverse: false,
+                range_end: None,
+            };
+            assert_eq!(to_argument(&options), vec!["1350/01/01".to_string()]);
+        }
+    }
+    mod write_lines {
+        use super::*;
+
+        struct ClosedPipe;
+
+        impl Write for ClosedPipe {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::from(ErrorKind::BrokenPipe))
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        #[test]
+        fn checking_value() {
+            let mut out = Vec::new();
+            write_lines(
+                &mut out,
+                &["Result: 0".to_string(), "Value0: a".to_string()],
+            )
+            .unwrap();
+            assert_eq!(String::from_utf8(out).unwrap(), "Result: 0\nValue0: a\n");
+        }
+
+        #[test]
+        fn return_broken_pipe_without_panic_when_pipe_is_closed() {
+            let result = write_lines(&mut ClosedPipe, &["Result: 0".to_string()]);
+            assert_eq!(result.unwrap_err().kind(), ErrorKind::BrokenPipe);
+        }
+    }
+}
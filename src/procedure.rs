@@ -1,18 +1,50 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use chrono::NaiveDate;
+use chrono::{DateTime, FixedOffset, NaiveTime, TimeDelta};
 
-use crate::represent::represent_by_gregorian;
+use crate::cache;
+use crate::elapsed::{elapsed_days, elapsed_years, last_day, span_days};
+use crate::kanshi::{day_kanshi, year_kanshi};
+use crate::lint::lint_spans;
+use crate::represent::{
+    calendar_era_year, calendar_year, represent_by_gregorian, represent_by_julian,
+    represent_by_lunisolar, represent_by_year_count, represent_reign, represent_span, Calendar,
+    Layout, NameStyle, NumberStyle, Style, YearCount,
+};
 use crate::request::*;
 use crate::response::*;
-use crate::search::search_from_array;
+use crate::reverse::reverse_from_array;
+use crate::search::{
+    is_covered, search_all_from_array, search_by_initial, search_by_name, search_from_array,
+    search_from_array_at, search_neighbor_from_array, search_range_from_array, Direction,
+    SearchedSpanList,
+};
 use crate::span;
+use crate::target::{parse_target, parse_target_time, target_len};
+use crate::validate::{validate_spans, ValidationIssue};
+
+const COMMAND_REVERSE: &str = "reverse";
+const COMMAND_RELOAD: &str = "reload";
+const COMMAND_VALIDATE: &str = "validate";
+const COMMAND_LINT: &str = "lint";
+const COMMAND_RANGE: &str = "range";
+const COMMAND_LIST: &str = "list";
+const COMMAND_LOOKUP: &str = "lookup";
+const COMMAND_PREVIOUS: &str = "previous";
+const COMMAND_NEXT: &str = "next";
 
 /// load時に呼ばれる関数
-pub fn load(_path: &str) {}
+pub fn load(path: &str) {
+    // 失敗したときはEXECUTE時に改めて読み込み、エラーを返す
+    let _ = cache::load_spans(&lists_path(path));
+}
 
 /// unload時に呼ばれる関数
-pub fn unload(_path: &str) {}
+pub fn unload(path: &str) {
+    let lists_path = lists_path(path);
+    cache::remove(&lists_path.join(span::TENNOU_DIR_PATH));
+    cache::remove(&lists_path);
+}
 
 /// request GET Version時に呼ばれる関数
 pub fn get_version(_path: &str, _request: &SaoriRequest, response: &mut SaoriResponse) {
@@ -20,15 +52,309 @@ pub fn get_version(_path: &str, _request: &SaoriRequest, response: &mut SaoriRes
 }
 
 /// request EXECUTE時に呼ばれる関数
-/// メインの処理はここに記述する
 pub fn execute(path: &str, request: &SaoriRequest, response: &mut SaoriResponse) {
+    execute_with_lists(&lists_path(path), request, response);
+}
+
+/// `gengou_lists`の場所を指定してrequest EXECUTEを処理する関数
+/// メインの処理はここに記述する
+pub fn execute_with_lists(lists_path: &Path, request: &SaoriRequest, response: &mut SaoriResponse) {
     let args = request.argument();
+
+    if args.first().is_some_and(|v| v == COMMAND_RELOAD) {
+        execute_reload(lists_path, response);
+        return;
+    }
+    if args.first().is_some_and(|v| v == COMMAND_VALIDATE) {
+        execute_validate(lists_path, response);
+        return;
+    }
+    if args.first().is_some_and(|v| v == COMMAND_LINT) {
+        execute_lint(lists_path, response);
+        return;
+    }
+
+    let span_list_array = match cache::load_spans(lists_path) {
+        Ok(r) => r,
+        Err(e) => {
+            response.set_result(format!("Error: {}", e));
+            return;
+        }
+    };
+
+    if args.first().is_some_and(|v| v == COMMAND_REVERSE) {
+        execute_reverse(&span_list_array, &args[1..], response);
+        return;
+    }
+    if args.first().is_some_and(|v| v == COMMAND_RANGE) {
+        execute_range(&span_list_array, &args[1..], response);
+        return;
+    }
+    if args.first().is_some_and(|v| v == COMMAND_LIST) {
+        execute_list(&span_list_array, &args[1..], response);
+        return;
+    }
+    if args.first().is_some_and(|v| v == COMMAND_LOOKUP) {
+        execute_lookup(&span_list_array, &args[1..], response);
+        return;
+    }
+    if args.first().is_some_and(|v| v == COMMAND_PREVIOUS) {
+        execute_neighbor(&span_list_array, &args[1..], Direction::Previous, response);
+        return;
+    }
+    if args.first().is_some_and(|v| v == COMMAND_NEXT) {
+        execute_neighbor(&span_list_array, &args[1..], Direction::Next, response);
+        return;
+    }
+
+    // 日付の後の引数は、日付の指定の仕方によって位置がずれる
+    let mut args_iter = args.iter().skip(target_len(args));
+
+    let mode_str = args_iter.next().map_or("Gi*", |s| s.as_str());
+    let mut should_search_future = true;
+    if mode_str.contains('*') {
+        should_search_future = true;
+    } else if mode_str.contains('!') {
+        should_search_future = false;
+    }
+
+    let mut number_style = NumberStyle::HalfWidth;
+    if mode_str.contains('i') {
+        number_style = NumberStyle::HalfWidth;
+    } else if mode_str.contains('k') {
+        number_style = NumberStyle::Kansuuji;
+    } else if mode_str.contains('z') {
+        number_style = NumberStyle::FullWidth;
+    } else if mode_str.contains('o') {
+        number_style = NumberStyle::PositionalKansuuji;
+    }
+
+    let mut name_style = NameStyle::Kanji;
+    if mode_str.contains('j') {
+        name_style = NameStyle::Kanji;
+    } else if mode_str.contains('h') {
+        name_style = NameStyle::Reading;
+    } else if mode_str.contains('r') {
+        name_style = NameStyle::Romaji;
+    } else if mode_str.contains('v') {
+        name_style = NameStyle::Variant;
+    } else if mode_str.contains('a') {
+        name_style = NameStyle::Letter;
+    }
+
+    let mut use_month_names = false;
+    if mode_str.contains('m') {
+        use_month_names = false;
+    } else if mode_str.contains('w') {
+        use_month_names = true;
+    }
+
+    let mut use_day_names = false;
+    if mode_str.contains('d') {
+        use_day_names = false;
+    } else if mode_str.contains('t') {
+        use_day_names = true;
+    }
+
+    let mut layout = Layout::Japanese;
+    if mode_str.contains('J') {
+        layout = Layout::Japanese;
+    } else if mode_str.contains('.') {
+        layout = Layout::Dotted;
+    } else if mode_str.contains('/') {
+        layout = Layout::Slashed;
+    }
+
+    let mut calendar = Calendar::Gregorian;
+    if mode_str.contains('G') {
+        calendar = Calendar::Gregorian;
+    } else if mode_str.contains('I') {
+        calendar = Calendar::Lunisolar;
+    } else if mode_str.contains('U') {
+        calendar = Calendar::Julian;
+    }
+
+    let mut is_julian_input = false;
+    if mode_str.contains('g') {
+        is_julian_input = false;
+    } else if mode_str.contains('u') {
+        is_julian_input = true;
+    }
+
+    // 元号とは別に、指定された全ての紀年法で表す
+    let year_counts: Vec<YearCount> = [
+        ('K', YearCount::Kouki),
+        ('B', YearCount::Butsureki),
+        ('S', YearCount::Seireki),
+    ]
+    .into_iter()
+    .filter(|(c, _)| mode_str.contains(*c))
+    .map(|(_, v)| v)
+    .collect();
+
+    let selector: Vec<&str> = args_iter
+        .next()
+        .map(|s| s.as_str())
+        .unwrap_or("")
+        .split('_')
+        .collect();
+
+    let pattern = args_iter.next().filter(|s| !s.is_empty()).cloned();
+
+    let time_str = args_iter.next().filter(|s| !s.is_empty());
+
+    let style = Style {
+        number: number_style,
+        name: name_style,
+        layout,
+        use_month_names,
+        use_day_names,
+        pattern,
+    };
+
+    let now_time = chrono::Local::now();
+    let (date, mut instant) = match parse_target(args, &now_time, is_julian_input) {
+        Ok(r) => r,
+        Err(e) => {
+            response.set_result(format!("Error: {}", e));
+            return;
+        }
+    };
+    // 時刻の引数は、日付の指定に含まれる時刻より優先する
+    if let Some(time_str) = time_str {
+        match parse_target_time(time_str, &date) {
+            Ok(i) => instant = Some(i),
+            Err(e) => {
+                response.set_result(format!("Error: {}", e));
+                return;
+            }
+        }
+    }
+
+    let now = now_time.date_naive();
+    let search_target_date = if should_search_future && date > now {
+        &now
+    } else {
+        &date
+    };
+    let search_target_instant =
+        instant.map(|i| clamp_instant(i, &now_time.fixed_offset(), should_search_future));
+    let span_list = match &search_target_instant {
+        Some(i) => search_from_array_at(&span_list_array, i, &selector),
+        None => search_from_array(&span_list_array, search_target_date, &selector),
+    };
+
+    let (r_date, r_spans) = match calendar {
+        Calendar::Gregorian => represent_by_gregorian(&span_list, &date, &style),
+        Calendar::Lunisolar => represent_by_lunisolar(&span_list, &date, &style),
+        Calendar::Julian => represent_by_julian(&span_list, &date, &style),
+    };
+
+    let r_year_counts = represent_by_year_count(&year_counts, &date, calendar, &style);
+
+    // 元号があてはまらないときは、紀年法で表した日付にする
+    let result = r_date
+        .first()
+        .or(r_year_counts.first())
+        .unwrap_or(&"".to_string())
+        .clone();
+    let value_1 = r_date.join(",");
+    let value_2 = r_spans.join(",");
+    // 干支は暦の年(太陰太陽暦なら旧暦の年)から求める
+    let value_3 = format!(
+        "{},{}",
+        year_kanshi(calendar_year(&date, calendar)),
+        day_kanshi(&date)
+    );
+
+    // 天皇は、元号と同じ頭文字のファイル(北朝・南朝など)から探す
+    // 天皇の一覧がない期間は、天皇がいない期間(空)と区別して`?`にする
+    let tennou_array = cache::load_spans(&lists_path.join(span::TENNOU_DIR_PATH))
+        .ok()
+        .filter(|a| is_covered(a, search_target_date));
+    let value_4 = span_list
+        .iter()
+        .flat_map(|(_, initial, spans)| spans.iter().map(move |_| *initial))
+        .map(|initial| {
+            tennou_array.as_ref().map_or("?".to_string(), |a| {
+                represent_reign(&search_by_initial(
+                    a,
+                    search_target_date,
+                    search_target_instant.as_ref(),
+                    initial,
+                ))
+            })
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let value_5 = r_year_counts.join(",");
+
+    // 元号の年・始期からの経過日数と満年数・期間の日数・最後の日も、Value0の元号と同じ順に入れる
+    let matched_spans: Vec<&span::Span> = span_list
+        .iter()
+        .flat_map(|(_, _, spans)| spans.iter().copied())
+        .collect();
+    let join_spans = |f: &dyn Fn(&span::Span) -> String| {
+        matched_spans
+            .iter()
+            .map(|v| f(v))
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+    let value_6 = join_spans(&|v| calendar_era_year(v, &date, calendar).to_string());
+    let value_7 = join_spans(&|v| elapsed_days(v, &date).to_string());
+    let value_8 = join_spans(&|v| elapsed_years(v, &date).to_string());
+    let value_9 = join_spans(&|v| span_days(v, &now).to_string());
+    let value_10 =
+        join_spans(&|v| last_day(v).map_or(String::new(), |d| d.format("%Y-%m-%d").to_string()));
+
+    response.set_result(result);
+    if !value_1.is_empty() || !value_5.is_empty() {
+        response.set_value(vec![
+            value_1, value_2, value_3, value_4, value_5, value_6, value_7, value_8, value_9,
+            value_10,
+        ]);
+    }
+}
+
+/// `****`の元号は今の時点まで続いているので、今(`*`なら未来も含む)の時点は、
+/// `****`が表す日(PCの今日)の終わり(日本標準時)までに収める
+fn clamp_instant(
+    instant: DateTime<FixedOffset>,
+    now: &DateTime<FixedOffset>,
+    should_search_future: bool,
+) -> DateTime<FixedOffset> {
+    let today_end = now
+        .date_naive()
+        .succ_opt()
+        .and_then(|d| {
+            d.and_time(NaiveTime::MIN)
+                .and_local_timezone(span::default_offset())
+                .single()
+        })
+        .map(|v| v - TimeDelta::seconds(1));
+
+    match today_end {
+        Some(end) if instant > end && (should_search_future || instant <= *now) => end,
+        _ => instant,
+    }
+}
+
+/// DLLのパスから`gengou_lists`のパスを求める
+fn lists_path(path: &str) -> PathBuf {
     let mut path = PathBuf::from(path);
     if !path.is_dir() {
         path.pop();
     }
 
-    let span_list_array = match span::load_spans(&path) {
+    path.join(span::SPAN_DIR_PATH)
+}
+
+/// `gengou_lists`内のファイルを読み込み直す
+fn execute_reload(lists_path: &Path, response: &mut SaoriResponse) {
+    // 天皇の一覧も、あれば一緒に読み込み直す
+    let span_list_array = match cache::reload_spans(lists_path) {
         Ok(r) => r,
         Err(e) => {
             response.set_result(format!("Error: {}", e));
@@ -36,39 +362,62 @@ pub fn execute(path: &str, request: &SaoriRequest, response: &mut SaoriResponse)
         }
     };
 
-    let mut args_iter = args.iter();
+    let files_count: usize = span_list_array.iter().map(|(_, m)| m.len()).sum();
+    let spans_count: usize = span_list_array
+        .iter()
+        .flat_map(|(_, m)| m.values())
+        .map(|l| l.spans().len())
+        .sum();
 
-    let (year, month, day) = match (args_iter.next(), args_iter.next(), args_iter.next()) {
-        (Some(year_str), Some(month_str), Some(day_str)) => {
-            match (
-                year_str.parse::<i32>(),
-                month_str.parse::<u32>(),
-                day_str.parse::<u32>(),
-            ) {
-                (Ok(y), Ok(m), Ok(d)) => (y, m, d),
-                (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
-                    response.set_result(format!("Error: {}", e));
-                    return;
-                }
-            }
-        }
-        _ => {
-            response.set_result(
-                "Error: arguments are not enough. arguments are required >= 3.".to_string(),
-            );
+    response.set_result("OK".to_string());
+    response.set_value(vec![files_count.to_string(), spans_count.to_string()]);
+}
+
+/// `gengou_lists`内のファイルを検証し、見つかった問題を全て返す
+fn execute_validate(lists_path: &Path, response: &mut SaoriResponse) {
+    set_issues(validate_spans(lists_path), response);
+}
+
+/// `gengou_lists`内のファイル全体を監査し、見つかった問題を全て返す
+fn execute_lint(lists_path: &Path, response: &mut SaoriResponse) {
+    set_issues(lint_spans(lists_path), response);
+}
+
+/// Resultに問題の数、Valueに問題の一覧を入れる
+fn set_issues(issues: Result<Vec<ValidationIssue>, std::io::Error>, response: &mut SaoriResponse) {
+    let issues = match issues {
+        Ok(r) => r,
+        Err(e) => {
+            response.set_result(format!("Error: {}", e));
             return;
         }
     };
 
-    let date = match NaiveDate::from_ymd_opt(year, month, day) {
-        Some(d) => d,
+    response.set_result(issues.len().to_string());
+    if !issues.is_empty() {
+        response.set_value(issues.iter().map(|v| v.to_string()).collect());
+    }
+}
+
+/// 元号を使用した日付からグレゴリオ暦の日付を割り出す
+fn execute_reverse(
+    span_list_array: &span::SpanListArray,
+    args: &[String],
+    response: &mut SaoriResponse,
+) {
+    let mut args_iter = args.iter();
+
+    let target = match args_iter.next() {
+        Some(v) => v,
         None => {
-            response.set_result("Error: target date is invalid.".to_string());
+            response.set_result(
+                "Error: arguments are not enough. arguments are required >= 2.".to_string(),
+            );
             return;
         }
     };
 
-    let mode_str = args_iter.next().map_or("Gi*", |s| s.as_str());
+    let mode_str = args_iter.next().map_or("G*", |s| s.as_str());
     let mut should_search_future = true;
     if mode_str.contains('*') {
         should_search_future = true;
@@ -76,21 +425,13 @@ pub fn execute(path: &str, request: &SaoriRequest, response: &mut SaoriResponse)
         should_search_future = false;
     }
 
-    let mut is_kansuuji = false;
-    if mode_str.contains('i') {
-        is_kansuuji = false;
-    } else if mode_str.contains('k') {
-        is_kansuuji = true;
+    let mut is_lunisolar = false;
+    if mode_str.contains('G') {
+        is_lunisolar = false;
+    } else if mode_str.contains('I') {
+        is_lunisolar = true;
     }
 
-    // TODO:
-    // let mut taiinreki_mode = false;
-    // if mode_str.contains('G') {
-    //     taiinreki_mode = false;
-    // } else if mode_str.contains('T') {
-    //     taiinreki_mode = true;
-    // }
-
     let selector: Vec<&str> = args_iter
         .next()
         .map(|s| s.as_str())
@@ -98,15 +439,22 @@ pub fn execute(path: &str, request: &SaoriRequest, response: &mut SaoriResponse)
         .split('_')
         .collect();
 
-    let now = chrono::Local::now().date_naive();
-    let search_target_date = if should_search_future && date > now {
-        &now
-    } else {
-        &date
-    };
-    let span_list = search_from_array(&span_list_array, search_target_date, &selector);
+    let reversed = reverse_from_array(
+        span_list_array,
+        target,
+        &selector,
+        is_lunisolar,
+        should_search_future,
+    );
 
-    let (r_date, r_spans) = represent_by_gregorian(&span_list, &date, is_kansuuji);
+    let r_date: Vec<String> = reversed
+        .iter()
+        .map(|(d, _, _, _)| d.format("%Y-%m-%d").to_string())
+        .collect();
+    let r_spans: Vec<String> = reversed
+        .iter()
+        .map(|(_, file_span, initial, _)| represent_span(file_span, initial))
+        .collect();
 
     let result = r_date.first().unwrap_or(&"".to_string()).clone();
     let value_1 = r_date.join(",");
@@ -117,3 +465,256 @@ pub fn execute(path: &str, request: &SaoriRequest, response: &mut SaoriResponse)
         response.set_value(vec![value_1, value_2]);
     }
 }
+
+/// 期間と重なる元号を全て探し、期間内に切り詰めた範囲とともに返す
+fn execute_range(
+    span_list_array: &span::SpanListArray,
+    args: &[String],
+    response: &mut SaoriResponse,
+) {
+    let (start_str, end_str) = match args {
+        [s, e, ..] => (s, e),
+        _ => {
+            response.set_result(
+                "Error: arguments are not enough. arguments are required >= 3.".to_string(),
+            );
+            return;
+        }
+    };
+
+    let now = chrono::Local::now();
+    let mut dates = Vec::new();
+    for arg in [start_str, end_str] {
+        match parse_target(std::slice::from_ref(arg), &now, false) {
+            Ok((date, _)) => dates.push(date),
+            Err(e) => {
+                response.set_result(format!("Error: {}", e));
+                return;
+            }
+        }
+    }
+    // 逆に指定されたときは入れ替える
+    dates.sort();
+    let range = span::NaiveDateSpan::new(dates[0], dates[1]);
+
+    let selector: Vec<&str> = args.get(2).map_or("", |s| s.as_str()).split('_').collect();
+
+    let searched = search_range_from_array(span_list_array, &range, &selector);
+    let entries: Vec<(&span::NaiveDateSpan, char, &span::Span)> = searched
+        .iter()
+        .flat_map(|(file_span, initial, spans)| {
+            spans.iter().map(move |v| (*file_span, *initial, *v))
+        })
+        .collect();
+
+    let format_span = |v: &span::NaiveDateSpan| {
+        format!(
+            "{}_{}",
+            v.start().format("%Y-%m-%d"),
+            v.end().format("%Y-%m-%d")
+        )
+    };
+    let join = |f: &dyn Fn(&(&span::NaiveDateSpan, char, &span::Span)) -> String| {
+        entries.iter().map(f).collect::<Vec<String>>().join(",")
+    };
+    let value_1 = join(&|(_, _, v)| v.gengou().to_string());
+    let value_2 = join(&|(_, _, v)| {
+        v.span()
+            .intersection(&range)
+            .map_or(String::new(), |v| format_span(&v))
+    });
+    let value_3 = join(&|(file_span, initial, _)| represent_span(file_span, initial));
+    let value_4 = join(&|(_, _, v)| format_span(v.span()));
+
+    response.set_result(entries.len().to_string());
+    if !entries.is_empty() {
+        response.set_value(vec![value_1, value_2, value_3, value_4]);
+    }
+}
+
+/// 読み込んだファイル(`files`)または元号(`eras`)の一覧を返す
+fn execute_list(
+    span_list_array: &span::SpanListArray,
+    args: &[String],
+    response: &mut SaoriResponse,
+) {
+    let target = args.first().map_or("", |s| s.as_str());
+    let is_eras = match target {
+        "" | "files" => false,
+        "eras" => true,
+        _ => {
+            response.set_result(format!(
+                "Error: list target '{}' is invalid. use 'files' or 'eras'.",
+                target
+            ));
+            return;
+        }
+    };
+
+    let selector: Vec<&str> = args.get(1).map_or("", |s| s.as_str()).split('_').collect();
+
+    let index = match args.get(2).filter(|s| !s.is_empty()) {
+        Some(s) => match s.parse::<usize>() {
+            Ok(v) if v < span_list_array.len() => Some(v),
+            Ok(_) => {
+                response.set_result(format!(
+                    "Error: range index '{}' is out of range. the number of ranges is {}.",
+                    s,
+                    span_list_array.len()
+                ));
+                return;
+            }
+            Err(_) => {
+                response.set_result(format!("Error: range index '{}' is invalid.", s));
+                return;
+            }
+        },
+        None => None,
+    };
+
+    let searched = search_all_from_array(span_list_array, &selector, index);
+    let lines: Vec<String> = if is_eras {
+        // 元号のない期間は含めない
+        let eras: SearchedSpanList<'_> = searched
+            .into_iter()
+            .map(|(file_span, initial, spans)| {
+                let spans = spans
+                    .into_iter()
+                    .filter(|v| !v.gengou().is_empty())
+                    .collect();
+                (file_span, initial, spans)
+            })
+            .collect();
+        represent_eras(&eras)
+    } else {
+        searched
+            .iter()
+            .map(|(file_span, initial, _)| represent_span(file_span, initial))
+            .collect()
+    };
+
+    response.set_result(lines.len().to_string());
+    if !lines.is_empty() {
+        response.set_value(lines);
+    }
+}
+
+/// 名前(読み・異表記を含む)から元号を探す。近いものを候補として返す
+fn execute_lookup(
+    span_list_array: &span::SpanListArray,
+    args: &[String],
+    response: &mut SaoriResponse,
+) {
+    let name = match args.first() {
+        Some(v) => v,
+        None => {
+            response.set_result(
+                "Error: arguments are not enough. arguments are required >= 2.".to_string(),
+            );
+            return;
+        }
+    };
+
+    let selector: Vec<&str> = args.get(1).map_or("", |s| s.as_str()).split('_').collect();
+
+    let (name_match, searched) = search_by_name(span_list_array, name, &selector);
+    let lines = represent_eras(&searched);
+
+    // 一致の仕方は全て同じなので、Resultにまとめて入れる
+    response.set_result(name_match.map_or(String::new(), |v| v.to_string()));
+    if !lines.is_empty() {
+        response.set_value(lines);
+    }
+}
+
+/// 元号の前または次の元号を探す
+fn execute_neighbor(
+    span_list_array: &span::SpanListArray,
+    args: &[String],
+    direction: Direction,
+    response: &mut SaoriResponse,
+) {
+    let gengou = match args.first() {
+        Some(v) => v,
+        None => {
+            response.set_result(
+                "Error: arguments are not enough. arguments are required >= 2.".to_string(),
+            );
+            return;
+        }
+    };
+
+    let selector: Vec<&str> = args.get(1).map_or("", |s| s.as_str()).split('_').collect();
+
+    let searched = search_neighbor_from_array(span_list_array, gengou, direction, &selector);
+    let result = searched
+        .iter()
+        .flat_map(|(_, _, spans)| spans.iter())
+        .next()
+        .map_or(String::new(), |v| v.gengou().to_string());
+    let lines = represent_eras(&searched);
+
+    response.set_result(result);
+    if !lines.is_empty() {
+        response.set_value(lines);
+    }
+}
+
+/// 元号ごとに`元号,%Y-%m-%d_%Y-%m-%d(元号の期間),ファイルの情報`の形式にする
+fn represent_eras(searched: &SearchedSpanList<'_>) -> Vec<String> {
+    searched
+        .iter()
+        .flat_map(|(file_span, initial, spans)| {
+            spans.iter().map(move |v| {
+                format!(
+                    "{},{}_{},{}",
+                    v.gengou(),
+                    v.span().start().format("%Y-%m-%d"),
+                    v.span().end().format("%Y-%m-%d"),
+                    represent_span(file_span, initial)
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    mod execute_list {
+        use super::*;
+
+        fn execute(args: &[&str]) -> SaoriResponse {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(span::SPAN_DIR_PATH);
+            let array = span::load_spans(&path).unwrap();
+            let request = SaoriRequest::new(SaoriCommand::Execute, vec![]);
+            let mut response = SaoriResponse::from_request(&request);
+            let args: Vec<String> = args.iter().map(|v| v.to_string()).collect();
+            execute_list(&array, &args, &mut response);
+            response
+        }
+
+        #[test]
+        fn checking_value_with_index() {
+            let response = execute(&["files", "*", "1"]);
+            assert_eq!(response.result(), "2");
+            assert_eq!(
+                response.value(),
+                &["1329-09-30_1394-08-10_h", "1329-09-30_1394-08-10_n"]
+            );
+        }
+
+        #[test]
+        fn failed_when_index_is_out_of_range() {
+            let response = execute(&["eras", "*", "99"]);
+            assert!(response.result().starts_with("Error:"));
+            assert!(response.value().is_empty());
+
+            let response = execute(&["files", "*", "5"]);
+            assert!(response.result().starts_with("Error:"));
+        }
+    }
+}
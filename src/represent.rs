@@ -1,33 +1,229 @@
 use chrono::{Datelike, NaiveDate};
 
-use crate::{search::SearchedSpanList, span::NaiveDateSpan};
+use crate::{
+    julian::JulianDate,
+    kanshi::{day_kanshi, year_kanshi},
+    lunisolar::LunisolarDate,
+    search::SearchedSpanList,
+    span::{CountingRule, NaiveDateSpan, Span},
+};
+
+/// 元号の表し方
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum NameStyle {
+    /// 漢字
+    #[default]
+    Kanji,
+    /// 読み(ひらがな)
+    Reading,
+    /// ローマ字表記
+    Romaji,
+    /// 異表記(旧字体など)
+    Variant,
+    /// ローマ字表記の頭文字(M/T/S/H/R)
+    Letter,
+}
+
+/// 出力する日付の暦
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum Calendar {
+    /// グレゴリオ暦(先発グレゴリオ暦)
+    #[default]
+    Gregorian,
+    /// 太陰太陽暦(旧暦)。改暦後はグレゴリオ暦
+    Lunisolar,
+    /// ユリウス暦
+    Julian,
+}
+
+/// 数字の表し方
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum NumberStyle {
+    /// 半角数字
+    #[default]
+    HalfWidth,
+    /// 漢数字
+    Kansuuji,
+    /// 全角数字
+    FullWidth,
+    /// 〇を使って位取りする漢数字(`二〇二三`)
+    PositionalKansuuji,
+}
+
+/// 年月日の並べ方
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum Layout {
+    /// `令和5年10月1日`
+    #[default]
+    Japanese,
+    /// `令和5.10.1`
+    Dotted,
+    /// `令和5/10/1`
+    Slashed,
+}
+
+/// 元号によらない紀年法
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum YearCount {
+    /// 皇紀(西暦+660)
+    Kouki,
+    /// 仏暦(西暦+543)
+    Butsureki,
+    /// 西暦(名前を付けない)
+    Seireki,
+}
+
+impl YearCount {
+    /// 西暦の年との差
+    fn offset(&self) -> i32 {
+        match self {
+            YearCount::Kouki => 660,
+            YearCount::Butsureki => 543,
+            YearCount::Seireki => 0,
+        }
+    }
+
+    /// 暦の年`year`を、元号と同じように表すための名前と年にする。期間は使用しない。
+    /// 西暦の0年以前は紀元前の年にする(0年が紀元前1年)。皇紀・仏暦の0年以前はそのまま(負の数)にする
+    fn span_and_year(&self, year: i32) -> (Span, i32) {
+        let year_i = year + self.offset();
+        let (name, reading, romaji, year_i) = match self {
+            YearCount::Kouki => ("皇紀", "こうき", "Koki", year_i),
+            YearCount::Butsureki => ("仏暦", "ぶつれき", "Butsureki", year_i),
+            YearCount::Seireki if year_i <= 0 => ("紀元前", "きげんぜん", "BC", 1 - year_i),
+            YearCount::Seireki => ("", "", "", year_i),
+        };
+        let span = Span::new(name.to_string(), NaiveDate::MIN, NaiveDate::MAX).with_names(
+            Some(reading.to_string()),
+            Some(romaji.to_string()),
+            vec![],
+        );
+        (span, year_i)
+    }
+}
+
+/// 日付の表し方
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct Style {
+    pub number: NumberStyle,
+    pub name: NameStyle,
+    pub layout: Layout,
+    /// 月を和風月名(睦月…師走)で表す
+    pub use_month_names: bool,
+    /// 1日を朔日、月の最後の日を晦日で表す
+    pub use_day_names: bool,
+    /// 指定されたときは`layout`より優先する
+    pub pattern: Option<String>,
+}
+
+/// 表す日付の各要素
+#[derive(Debug, Clone, PartialEq)]
+struct DateParts {
+    /// グレゴリオ暦の日付
+    date: NaiveDate,
+    /// 暦の年(太陰太陽暦なら旧暦の年)
+    year: i32,
+    year_i: i32,
+    month: u32,
+    /// 太陰太陽暦の月のときのみ指定する
+    is_leap: Option<bool>,
+    day: u32,
+    is_last_day: bool,
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "睦月",
+    "如月",
+    "弥生",
+    "卯月",
+    "皐月",
+    "水無月",
+    "文月",
+    "葉月",
+    "長月",
+    "神無月",
+    "霜月",
+    "師走",
+];
+
+const MONTHS_EN: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
 
 pub(crate) fn represent_by_gregorian(
     searched_list: &SearchedSpanList<'_>,
     date: &NaiveDate,
-    is_kansuuji: bool,
+    style: &Style,
 ) -> (Vec<String>, Vec<String>) {
     let mut r_dates = Vec::new();
     let mut r_spans = Vec::new();
 
     for (file_span, initial, spans) in searched_list.iter() {
         for span in spans.iter() {
-            let year_i = date.year() - span.span().start().year() + 1;
-            let year = if year_i == 1 {
-                "元".to_string()
-            } else if is_kansuuji {
-                to_kansuuji(year_i as u32)
-            } else {
-                format!("{}", year_i)
+            let parts = DateParts {
+                year_i: calendar_era_year(span, date, Calendar::Gregorian),
+                ..gregorian_parts(date)
             };
+            let date_str = represent_date(span, &parts, style);
+
+            r_dates.push(date_str);
+            r_spans.push(represent_span(file_span, initial));
+        }
+    }
 
-            let (month, day) = if is_kansuuji {
-                (to_kansuuji(date.month()), to_kansuuji(date.day()))
-            } else {
-                (date.month().to_string(), date.day().to_string())
+    (r_dates, r_spans)
+}
+
+/// 太陰太陽暦(旧暦)で日付を表す
+/// 改暦(1873-01-01)以後の日付はグレゴリオ暦で表す
+pub(crate) fn represent_by_lunisolar(
+    searched_list: &SearchedSpanList<'_>,
+    date: &NaiveDate,
+    style: &Style,
+) -> (Vec<String>, Vec<String>) {
+    let lunisolar_parts = if let Some(p) = lunisolar_parts(date) {
+        p
+    } else {
+        return represent_by_gregorian(searched_list, date, style);
+    };
+
+    let mut r_dates = Vec::new();
+    let mut r_spans = Vec::new();
+
+    for (file_span, initial, spans) in searched_list.iter() {
+        for span in spans.iter() {
+            let parts = DateParts {
+                year_i: calendar_era_year(span, date, Calendar::Lunisolar),
+                ..lunisolar_parts.clone()
             };
+            let date_str = represent_date(span, &parts, style);
+
+            r_dates.push(date_str);
+            r_spans.push(represent_span(file_span, initial));
+        }
+    }
 
-            let date_str = format!("{}{}年{}月{}日", span.gengou(), year, month, day,);
+    (r_dates, r_spans)
+}
+
+/// ユリウス暦で日付を表す。元号の年は、始期のユリウス暦の年から数える
+pub(crate) fn represent_by_julian(
+    searched_list: &SearchedSpanList<'_>,
+    date: &NaiveDate,
+    style: &Style,
+) -> (Vec<String>, Vec<String>) {
+    let julian_parts = julian_parts(date);
+
+    let mut r_dates = Vec::new();
+    let mut r_spans = Vec::new();
+
+    for (file_span, initial, spans) in searched_list.iter() {
+        for span in spans.iter() {
+            let parts = DateParts {
+                year_i: calendar_era_year(span, date, Calendar::Julian),
+                ..julian_parts.clone()
+            };
+            let date_str = represent_date(span, &parts, style);
 
             r_dates.push(date_str);
             r_spans.push(represent_span(file_span, initial));
@@ -37,7 +233,268 @@ pub(crate) fn represent_by_gregorian(
     (r_dates, r_spans)
 }
 
-fn represent_span(file_span: &NaiveDateSpan, initial: &char) -> String {
+/// `calendar`で表したときの元号の年。
+/// 数え方(属性`rule`)は旧暦で表すときのみ使用し、グレゴリオ暦・ユリウス暦では暦の年で数える
+pub(crate) fn calendar_era_year(span: &Span, date: &NaiveDate, calendar: Calendar) -> i32 {
+    let is_yunen = calendar == Calendar::Lunisolar && span.counting_rule() == CountingRule::Yunen;
+    // 改暦以後など旧暦を計算しない日付は太陰太陽暦でもグレゴリオ暦で表す
+    let calendar = match calendar {
+        Calendar::Lunisolar if lunisolar_parts(date).is_none() => Calendar::Gregorian,
+        v => v,
+    };
+    let years = calendar_year(date, calendar) - calendar_year(span.span().start(), calendar);
+    if is_yunen {
+        // 始期から翌年の正月までも元年とする
+        years.max(1)
+    } else {
+        years + 1
+    }
+}
+
+/// 元号によらない紀年法で日付を表す。一覧から探した元号とは関係なく、`counts`の順に返す
+pub(crate) fn represent_by_year_count(
+    counts: &[YearCount],
+    date: &NaiveDate,
+    calendar: Calendar,
+    style: &Style,
+) -> Vec<String> {
+    let parts = calendar_parts(date, calendar);
+
+    counts
+        .iter()
+        .map(|count| {
+            let (span, year_i) = count.span_and_year(parts.year);
+            let parts = DateParts {
+                year_i,
+                ..parts.clone()
+            };
+            // 紀年法の1年は元年としない
+            represent_date_with(&span, &parts, style, false)
+        })
+        .collect()
+}
+
+/// 暦の年(太陰太陽暦なら旧暦の年、ユリウス暦ならユリウス暦の年)
+pub(crate) fn calendar_year(date: &NaiveDate, calendar: Calendar) -> i32 {
+    calendar_parts(date, calendar).year
+}
+
+/// 指定された暦の日付の各要素。`year_i`は暦の年にしておく
+fn calendar_parts(date: &NaiveDate, calendar: Calendar) -> DateParts {
+    match calendar {
+        Calendar::Gregorian => gregorian_parts(date),
+        Calendar::Lunisolar => lunisolar_parts(date).unwrap_or_else(|| gregorian_parts(date)),
+        Calendar::Julian => julian_parts(date),
+    }
+}
+
+/// グレゴリオ暦の日付の各要素。`year_i`は西暦の年にしておく
+fn gregorian_parts(date: &NaiveDate) -> DateParts {
+    DateParts {
+        date: *date,
+        year: date.year(),
+        year_i: date.year(),
+        month: date.month(),
+        is_leap: None,
+        day: date.day(),
+        is_last_day: date.succ_opt().is_some_and(|v| v.day() == 1),
+    }
+}
+
+/// 太陰太陽暦の日付の各要素。`year_i`は旧暦の年にしておく。
+/// 改暦(1873-01-01)以後と、旧暦を計算する範囲より前は`None`
+fn lunisolar_parts(date: &NaiveDate) -> Option<DateParts> {
+    let lunisolar_date = LunisolarDate::from_gregorian(date)?;
+
+    // 翌日が改暦後なら、その月の最後の日とする(明治5年12月2日)
+    let is_last_day = date
+        .succ_opt()
+        .map(|v| LunisolarDate::from_gregorian(&v).is_none_or(|v| v.day() == 1))
+        .unwrap_or(false);
+
+    Some(DateParts {
+        date: *date,
+        year: lunisolar_date.year(),
+        year_i: lunisolar_date.year(),
+        month: lunisolar_date.month(),
+        is_leap: Some(lunisolar_date.is_leap()),
+        day: lunisolar_date.day(),
+        is_last_day,
+    })
+}
+
+/// ユリウス暦の日付の各要素。`year_i`はユリウス暦の年にしておく
+fn julian_parts(date: &NaiveDate) -> DateParts {
+    let julian_date = JulianDate::from_gregorian(date);
+
+    DateParts {
+        date: *date,
+        year: julian_date.year(),
+        year_i: julian_date.year(),
+        month: julian_date.month(),
+        is_leap: None,
+        day: julian_date.day(),
+        is_last_day: date
+            .succ_opt()
+            .is_some_and(|v| JulianDate::from_gregorian(&v).day() == 1),
+    }
+}
+
+fn represent_date(span: &Span, parts: &DateParts, style: &Style) -> String {
+    represent_date_with(span, parts, style, true)
+}
+
+/// `use_gannen`なら、1年を`%y`で表すときに元年とする
+fn represent_date_with(span: &Span, parts: &DateParts, style: &Style, use_gannen: bool) -> String {
+    let gengou = represent_name(span, &style.name);
+    let DateParts {
+        date,
+        year,
+        year_i,
+        month,
+        is_leap,
+        day,
+        is_last_day,
+    } = *parts;
+
+    let pattern = match (&style.pattern, &style.layout) {
+        (Some(p), _) => p.as_str(),
+        (None, Layout::Japanese) if style.name == NameStyle::Romaji => {
+            return represent_date_romaji(gengou, year_i, month, is_leap, day);
+        }
+        (None, Layout::Japanese) => "%G%y年%L%M%D",
+        (None, Layout::Dotted) => "%G%Y.%L%m.%d",
+        (None, Layout::Slashed) => "%G%Y/%L%m/%d",
+    };
+
+    let mut result = String::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('G') => result.push_str(gengou),
+            Some('g') => result.push_str(represent_name(span, &NameStyle::Letter)),
+            Some('Y') => result.push_str(&represent_year(year_i, &style.number)),
+            Some('y') if use_gannen && year_i == 1 => result.push('元'),
+            Some('y') => result.push_str(&represent_year(year_i, &style.number)),
+            Some('L') if is_leap == Some(true) => result.push('閏'),
+            Some('L') => {}
+            Some('m') => result.push_str(&represent_number(month, &style.number)),
+            Some('M') if style.use_month_names => result.push_str(MONTH_NAMES[month as usize - 1]),
+            Some('M') => {
+                result.push_str(&represent_number(month, &style.number));
+                result.push('月');
+            }
+            Some('d') => result.push_str(&represent_number(day, &style.number)),
+            Some('D') if style.use_day_names && day == 1 => result.push_str("朔日"),
+            Some('D') if style.use_day_names && is_last_day => result.push_str("晦日"),
+            Some('D') => {
+                result.push_str(&represent_number(day, &style.number));
+                result.push('日');
+            }
+            Some('K') => result.push_str(&year_kanshi(year)),
+            Some('k') => result.push_str(&day_kanshi(&date)),
+            Some('%') => result.push('%'),
+            // 不明な指定はそのまま出力する
+            Some(other) => {
+                result.push('%');
+                result.push(other);
+            }
+            None => result.push('%'),
+        }
+    }
+
+    result
+}
+
+/// 年を表す。皇紀・仏暦の0年以前は負の数になるので、数字の表記に合わせた符号を付ける
+/// (半角数字は`-`、全角数字は`－`、漢数字は`マイナス`)
+fn represent_year(year: i32, number_style: &NumberStyle) -> String {
+    let number = represent_number(year.unsigned_abs(), number_style);
+    if year >= 0 {
+        return number;
+    }
+
+    let sign = match number_style {
+        NumberStyle::HalfWidth => "-",
+        NumberStyle::FullWidth => "－",
+        NumberStyle::Kansuuji | NumberStyle::PositionalKansuuji => "マイナス",
+    };
+    format!("{}{}", sign, number)
+}
+
+fn represent_number(value: u32, number_style: &NumberStyle) -> String {
+    match number_style {
+        NumberStyle::HalfWidth => value.to_string(),
+        NumberStyle::Kansuuji => to_kansuuji(value),
+        NumberStyle::FullWidth => value
+            .to_string()
+            .chars()
+            .map(|c| char::from_u32(c as u32 - '0' as u32 + '０' as u32).unwrap_or(c))
+            .collect(),
+        NumberStyle::PositionalKansuuji => to_positional_kansuuji(value),
+    }
+}
+
+/// `Reiwa 5, Oct 1`のように表す。
+/// 太陰太陽暦の月は`Genroku 15, Month 12, Day 14`のように表す。
+fn represent_date_romaji(
+    gengou: &str,
+    year_i: i32,
+    month: u32,
+    is_leap: Option<bool>,
+    day: u32,
+) -> String {
+    let gengou_year = if gengou.is_empty() {
+        year_i.to_string()
+    } else {
+        format!("{} {}", gengou, year_i)
+    };
+
+    match is_leap {
+        None => format!("{}, {} {}", gengou_year, MONTHS_EN[month as usize - 1], day),
+        Some(is_leap) => format!(
+            "{}, {}Month {}, Day {}",
+            gengou_year,
+            if is_leap { "Leap " } else { "" },
+            month,
+            day
+        ),
+    }
+}
+
+/// 指定された表し方の元号名。なければ漢字の元号名にする
+fn represent_name<'a>(span: &'a Span, name_style: &NameStyle) -> &'a str {
+    let name = match name_style {
+        NameStyle::Kanji => None,
+        NameStyle::Reading => span.reading(),
+        NameStyle::Romaji => span.romaji(),
+        NameStyle::Variant => span.variants().first().map(|v| v.as_str()),
+        NameStyle::Letter => span
+            .romaji()
+            .and_then(|v| v.get(..1))
+            .filter(|v| v.chars().all(|c| c.is_ascii_uppercase())),
+    };
+    name.unwrap_or(span.gengou())
+}
+
+/// 天皇を`第126代今上天皇`のように表す。代数の属性(`reign`)がなければ名前のみにする。
+/// 複数あるときは`|`で区切る。
+pub(crate) fn represent_reign(spans: &[&Span]) -> String {
+    spans
+        .iter()
+        .map(|v| match v.attribute("reign") {
+            Some(reign) => format!("第{}代{}", reign, v.gengou()),
+            None => v.gengou().to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join("|")
+}
+
+pub(crate) fn represent_span(file_span: &NaiveDateSpan, initial: &char) -> String {
     format!(
         "{}_{}_{}",
         file_span.start().format("%Y-%m-%d"),
@@ -70,6 +527,11 @@ const PARTS: [&str; 18] = [
 ];
 
 fn to_kansuuji(value: u32) -> String {
+    // 位のない0は〇とする
+    if value == 0 {
+        return "〇".to_string();
+    }
+
     let mut buf: Vec<&'static str> = Vec::new();
 
     let v_str = value.to_string();
@@ -86,7 +548,7 @@ fn to_kansuuji(value: u32) -> String {
         }
 
         // 数字本体
-        if !(class % 4 != 0 && code == 1) {
+        if class.is_multiple_of(4) || code != 1 {
             buf.push(NUMS[code]);
         }
         // 十百千
@@ -94,7 +556,7 @@ fn to_kansuuji(value: u32) -> String {
             buf.push(SUBS[class % 4]);
         }
         // 万億兆……
-        if parts_flag && class % 4 == 0 {
+        if parts_flag && class.is_multiple_of(4) {
             buf.push(PARTS[class / 4]);
             parts_flag = false;
         }
@@ -103,6 +565,16 @@ fn to_kansuuji(value: u32) -> String {
     buf.join("")
 }
 
+const POSITIONAL_NUMS: [char; 10] = ['〇', '一', '二', '三', '四', '五', '六', '七', '八', '九'];
+
+fn to_positional_kansuuji(value: u32) -> String {
+    value
+        .to_string()
+        .bytes()
+        .map(|v| POSITIONAL_NUMS[(v - b'0') as usize])
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,9 +613,9 @@ mod tests {
             let searched_list = vec![(&file_span, 'a', spans_a), (&file_span, 'b', spans_b)];
 
             let date = NaiveDate::from_ymd_opt(150, 1, 1).unwrap();
-            let is_kansuuji = false;
+            let style = Style::default();
 
-            let (r_dates, r_spans) = represent_by_gregorian(&searched_list, &date, is_kansuuji);
+            let (r_dates, r_spans) = represent_by_gregorian(&searched_list, &date, &style);
             assert_eq!(
                 r_dates,
                 vec![
@@ -163,6 +635,641 @@ mod tests {
         }
     }
 
+    mod represent_with_counting_rule {
+        use crate::span::Span;
+
+        use super::*;
+
+        fn genroku(rule: Option<&str>) -> Span {
+            let attributes = rule
+                .map(|v| vec![("rule".to_string(), v.to_string())])
+                .unwrap_or_default();
+            Span::new(
+                "元禄".to_string(),
+                NaiveDate::from_ymd_opt(1688, 10, 23).unwrap(),
+                NaiveDate::from_ymd_opt(1704, 4, 16).unwrap(),
+            )
+            .with_attributes(attributes)
+        }
+
+        fn file_span() -> NaiveDateSpan {
+            NaiveDateSpan::new(
+                NaiveDate::from_ymd_opt(1573, 9, 4).unwrap(),
+                NaiveDate::from_ymd_opt(1868, 10, 23).unwrap(),
+            )
+        }
+
+        #[test]
+        fn checking_value_when_gregorian() {
+            // グレゴリオ暦では数え方によらず暦の年で数える
+            let cases = [
+                (None, (1703, 1, 30), "元禄16年1月30日"),
+                (Some("ritsunen"), (1703, 1, 30), "元禄16年1月30日"),
+                (Some("yunen"), (1703, 1, 30), "元禄16年1月30日"),
+                (Some("踰年改元"), (1688, 12, 1), "元禄元年12月1日"),
+                (Some("yunen"), (1689, 2, 20), "元禄2年2月20日"),
+            ];
+            for (rule, (y, m, d), expected) in cases.iter() {
+                let case = genroku(*rule);
+                let file_span = file_span();
+                let searched_list = vec![(&file_span, 'a', vec![&case])];
+                let date = NaiveDate::from_ymd_opt(*y, *m, *d).unwrap();
+
+                let (r_dates, _r_spans) =
+                    represent_by_gregorian(&searched_list, &date, &Style::default());
+                assert_eq!(r_dates, vec![expected.to_string()], "{:?}", rule);
+            }
+        }
+
+        #[test]
+        fn checking_value_when_lunisolar() {
+            let cases = [
+                (None, (1703, 1, 30), "元禄15年12月14日"),
+                (Some("立年改元"), (1703, 1, 30), "元禄15年12月14日"),
+                (Some("yunen"), (1703, 1, 30), "元禄14年12月14日"),
+                // 踰年改元では、始期から翌年の正月までも元年
+                (Some("踰年改元"), (1688, 12, 1), "元禄元年11月9日"),
+                (Some("yunen"), (1689, 2, 20), "元禄元年1月1日"),
+                // 不明な数え方は指定しないときと同じ
+                (Some("unknown"), (1689, 2, 20), "元禄2年1月1日"),
+            ];
+            for (rule, (y, m, d), expected) in cases.iter() {
+                let case = genroku(*rule);
+                let file_span = file_span();
+                let searched_list = vec![(&file_span, 'a', vec![&case])];
+                let date = NaiveDate::from_ymd_opt(*y, *m, *d).unwrap();
+
+                let (r_dates, _r_spans) =
+                    represent_by_lunisolar(&searched_list, &date, &Style::default());
+                assert_eq!(r_dates, vec![expected.to_string()], "{:?}", rule);
+            }
+        }
+
+        #[test]
+        fn checking_value_when_meiji() {
+            let case = Span::new(
+                "明治".to_string(),
+                NaiveDate::from_ymd_opt(1868, 1, 25).unwrap(),
+                NaiveDate::from_ymd_opt(1912, 7, 29).unwrap(),
+            )
+            .with_attributes(vec![("rule".to_string(), "立年改元".to_string())]);
+            let file_span = file_span();
+            let searched_list = vec![(&file_span, 'm', vec![&case])];
+            let date = NaiveDate::from_ymd_opt(1869, 1, 15).unwrap();
+
+            let (r_dates, _r_spans) =
+                represent_by_gregorian(&searched_list, &date, &Style::default());
+            assert_eq!(r_dates, vec!["明治2年1月15日".to_string()]);
+            let (r_dates, _r_spans) =
+                represent_by_lunisolar(&searched_list, &date, &Style::default());
+            assert_eq!(r_dates, vec!["明治元年12月3日".to_string()]);
+        }
+    }
+
+    mod represent_by_gregorian_with_names {
+        use crate::span::Span;
+
+        use super::*;
+
+        fn case_span() -> Span {
+            Span::new(
+                "令和".to_string(),
+                NaiveDate::from_ymd_opt(2019, 5, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2100, 1, 1).unwrap(),
+            )
+            .with_names(
+                Some("れいわ".to_string()),
+                Some("Reiwa".to_string()),
+                vec!["令咊".to_string()],
+            )
+        }
+
+        fn represent(span: &Span, name: NameStyle) -> String {
+            let file_span = NaiveDateSpan::new(
+                NaiveDate::from_ymd_opt(1868, 10, 23).unwrap(),
+                NaiveDate::from_ymd_opt(2100, 1, 1).unwrap(),
+            );
+            let searched_list = vec![(&file_span, 'a', vec![span])];
+            let date = NaiveDate::from_ymd_opt(2023, 10, 1).unwrap();
+            let style = Style {
+                name,
+                ..Default::default()
+            };
+
+            let (r_dates, _r_spans) = represent_by_gregorian(&searched_list, &date, &style);
+            r_dates[0].clone()
+        }
+
+        #[test]
+        fn checking_value_each_name_style() {
+            let span = case_span();
+            assert_eq!(represent(&span, NameStyle::Kanji), "令和5年10月1日");
+            assert_eq!(represent(&span, NameStyle::Reading), "れいわ5年10月1日");
+            assert_eq!(represent(&span, NameStyle::Romaji), "Reiwa 5, Oct 1");
+            assert_eq!(represent(&span, NameStyle::Variant), "令咊5年10月1日");
+        }
+
+        #[test]
+        fn checking_value_fallback_to_kanji_when_no_names() {
+            let span = Span::new(
+                "令和".to_string(),
+                NaiveDate::from_ymd_opt(2019, 5, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2100, 1, 1).unwrap(),
+            );
+            assert_eq!(represent(&span, NameStyle::Reading), "令和5年10月1日");
+            assert_eq!(represent(&span, NameStyle::Romaji), "令和 5, Oct 1");
+        }
+    }
+
+    mod represent_by_lunisolar {
+        use crate::span::Span;
+
+        use super::*;
+
+        #[test]
+        fn checking_value_before_gregorian_adoption() {
+            let file_span = NaiveDateSpan::new(
+                NaiveDate::from_ymd_opt(1573, 9, 4).unwrap(),
+                NaiveDate::from_ymd_opt(1868, 10, 23).unwrap(),
+            );
+            let case = Span::new(
+                "元禄".to_string(),
+                NaiveDate::from_ymd_opt(1688, 10, 23).unwrap(),
+                NaiveDate::from_ymd_opt(1704, 4, 16).unwrap(),
+            );
+            let searched_list = vec![(&file_span, 'a', vec![&case])];
+
+            let date = NaiveDate::from_ymd_opt(1703, 1, 30).unwrap();
+
+            let (r_dates, r_spans) =
+                represent_by_lunisolar(&searched_list, &date, &Style::default());
+            assert_eq!(r_dates, vec!["元禄15年12月14日".to_string()]);
+            assert_eq!(r_spans, vec!["1573-09-04_1868-10-23_a".to_string()]);
+
+            let (r_dates, _r_spans) = represent_by_lunisolar(
+                &searched_list,
+                &date,
+                &Style {
+                    number: NumberStyle::Kansuuji,
+                    ..Default::default()
+                },
+            );
+            assert_eq!(r_dates, vec!["元禄十五年十二月十四日".to_string()]);
+        }
+
+        #[test]
+        fn checking_value_leap_month() {
+            let file_span = NaiveDateSpan::new(
+                NaiveDate::from_ymd_opt(1573, 9, 4).unwrap(),
+                NaiveDate::from_ymd_opt(1868, 10, 23).unwrap(),
+            );
+            let case = Span::new(
+                "慶応".to_string(),
+                NaiveDate::from_ymd_opt(1865, 5, 1).unwrap(),
+                NaiveDate::from_ymd_opt(1868, 10, 23).unwrap(),
+            );
+            let searched_list = vec![(&file_span, 'a', vec![&case])];
+
+            let date = NaiveDate::from_ymd_opt(1865, 7, 1).unwrap();
+
+            let (r_dates, _r_spans) =
+                represent_by_lunisolar(&searched_list, &date, &Style::default());
+            assert_eq!(r_dates, vec!["慶応元年閏5月9日".to_string()]);
+
+            let style = Style {
+                name: NameStyle::Romaji,
+                ..Default::default()
+            };
+            let (r_dates, _r_spans) = represent_by_lunisolar(&searched_list, &date, &style);
+            assert_eq!(r_dates, vec!["慶応 1, Leap Month 5, Day 9".to_string()]);
+        }
+
+        #[test]
+        fn checking_value_after_gregorian_adoption() {
+            let file_span = NaiveDateSpan::new(
+                NaiveDate::from_ymd_opt(1868, 10, 23).unwrap(),
+                NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+            );
+            let case = Span::new(
+                "明治".to_string(),
+                NaiveDate::from_ymd_opt(1868, 1, 25).unwrap(),
+                NaiveDate::from_ymd_opt(1912, 7, 29).unwrap(),
+            );
+            let searched_list = vec![(&file_span, 'a', vec![&case])];
+
+            let date = NaiveDate::from_ymd_opt(1873, 1, 1).unwrap();
+
+            let (r_dates, _r_spans) =
+                represent_by_lunisolar(&searched_list, &date, &Style::default());
+            assert_eq!(r_dates, vec!["明治6年1月1日".to_string()]);
+
+            let date = NaiveDate::from_ymd_opt(1872, 12, 31).unwrap();
+
+            let (r_dates, _r_spans) =
+                represent_by_lunisolar(&searched_list, &date, &Style::default());
+            assert_eq!(r_dates, vec!["明治5年12月2日".to_string()]);
+        }
+    }
+
+    mod represent_date {
+        use crate::span::Span;
+
+        use super::*;
+
+        fn parts(year_i: i32, month: u32, is_leap: Option<bool>, day: u32) -> DateParts {
+            DateParts {
+                date: NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+                year: 1988 + year_i,
+                year_i,
+                month,
+                is_leap,
+                day,
+                is_last_day: false,
+            }
+        }
+
+        fn case_span() -> Span {
+            Span::new(
+                "平成".to_string(),
+                NaiveDate::from_ymd_opt(1989, 1, 8).unwrap(),
+                NaiveDate::from_ymd_opt(2019, 4, 30).unwrap(),
+            )
+            .with_names(
+                Some("へいせい".to_string()),
+                Some("Heisei".to_string()),
+                vec![],
+            )
+        }
+
+        #[test]
+        fn checking_value_layouts() {
+            let span = case_span();
+            let mut style = Style {
+                name: NameStyle::Letter,
+                layout: Layout::Dotted,
+                ..Default::default()
+            };
+            assert_eq!(
+                represent_date(&span, &parts(31, 4, None, 30), &style),
+                "H31.4.30"
+            );
+
+            style.layout = Layout::Slashed;
+            assert_eq!(
+                represent_date(&span, &parts(31, 4, None, 30), &style),
+                "H31/4/30"
+            );
+            assert_eq!(
+                represent_date(&span, &parts(1, 1, None, 8), &style),
+                "H1/1/8"
+            );
+
+            style.name = NameStyle::Kanji;
+            style.layout = Layout::Dotted;
+            assert_eq!(
+                represent_date(&span, &parts(31, 4, None, 30), &style),
+                "平成31.4.30"
+            );
+
+            style.layout = Layout::Japanese;
+            style.number = NumberStyle::FullWidth;
+            assert_eq!(
+                represent_date(&span, &parts(31, 4, None, 30), &style),
+                "平成３１年４月３０日"
+            );
+            assert_eq!(
+                represent_date(&span, &parts(1, 1, None, 8), &style),
+                "平成元年１月８日"
+            );
+        }
+
+        #[test]
+        fn checking_value_letter_fallback_to_kanji() {
+            let span = Span::new(
+                "慶応".to_string(),
+                NaiveDate::from_ymd_opt(1865, 5, 1).unwrap(),
+                NaiveDate::from_ymd_opt(1868, 10, 23).unwrap(),
+            );
+            let style = Style {
+                name: NameStyle::Letter,
+                layout: Layout::Dotted,
+                ..Default::default()
+            };
+            assert_eq!(
+                represent_date(&span, &parts(3, 1, None, 1), &style),
+                "慶応3.1.1"
+            );
+        }
+
+        #[test]
+        fn checking_value_pattern() {
+            let span = case_span();
+            let style = Style {
+                number: NumberStyle::Kansuuji,
+                layout: Layout::Dotted,
+                pattern: Some("%g%Y(%G%y年%K) %L%m月%d日%k 100%% %x%".to_string()),
+                ..Default::default()
+            };
+            assert_eq!(
+                represent_date(&span, &parts(1, 5, Some(true), 8), &style),
+                "H一(平成元年己巳) 閏五月八日戊午 100% %x%"
+            );
+            assert_eq!(
+                represent_date(&span, &parts(12, 5, Some(false), 8), &style),
+                "H十二(平成十二年庚辰) 五月八日戊午 100% %x%"
+            );
+        }
+    }
+
+    mod represent_by_lunisolar_with_traditional_names {
+        use crate::span::Span;
+
+        use super::*;
+
+        #[test]
+        fn checking_value_month_and_day_names() {
+            let file_span = NaiveDateSpan::new(
+                NaiveDate::from_ymd_opt(1573, 9, 4).unwrap(),
+                NaiveDate::from_ymd_opt(1868, 10, 23).unwrap(),
+            );
+            let case = Span::new(
+                "元禄".to_string(),
+                NaiveDate::from_ymd_opt(1688, 10, 23).unwrap(),
+                NaiveDate::from_ymd_opt(1704, 4, 16).unwrap(),
+            );
+            let searched_list = vec![(&file_span, 'a', vec![&case])];
+            let style = Style {
+                number: NumberStyle::Kansuuji,
+                use_month_names: true,
+                use_day_names: true,
+                ..Default::default()
+            };
+
+            let date = NaiveDate::from_ymd_opt(1703, 1, 30).unwrap();
+            let (r_dates, _r_spans) = represent_by_lunisolar(&searched_list, &date, &style);
+            assert_eq!(r_dates, vec!["元禄十五年師走十四日".to_string()]);
+
+            // 元禄16年1月1日
+            let date = NaiveDate::from_ymd_opt(1703, 2, 16).unwrap();
+            let (r_dates, _r_spans) = represent_by_lunisolar(&searched_list, &date, &style);
+            assert_eq!(r_dates, vec!["元禄十六年睦月朔日".to_string()]);
+
+            // 元禄15年12月の最後の日
+            let date = NaiveDate::from_ymd_opt(1703, 2, 15).unwrap();
+            let (r_dates, _r_spans) = represent_by_lunisolar(&searched_list, &date, &style);
+            assert_eq!(r_dates, vec!["元禄十五年師走晦日".to_string()]);
+        }
+
+        #[test]
+        fn checking_value_gregorian_last_day() {
+            let file_span = NaiveDateSpan::new(
+                NaiveDate::from_ymd_opt(1868, 10, 23).unwrap(),
+                NaiveDate::from_ymd_opt(2100, 1, 1).unwrap(),
+            );
+            let case = Span::new(
+                "令和".to_string(),
+                NaiveDate::from_ymd_opt(2019, 5, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2100, 1, 1).unwrap(),
+            );
+            let searched_list = vec![(&file_span, 'a', vec![&case])];
+            let style = Style {
+                use_day_names: true,
+                ..Default::default()
+            };
+
+            let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+            let (r_dates, _r_spans) = represent_by_gregorian(&searched_list, &date, &style);
+            assert_eq!(r_dates, vec!["令和6年2月晦日".to_string()]);
+
+            let date = NaiveDate::from_ymd_opt(2024, 2, 28).unwrap();
+            let (r_dates, _r_spans) = represent_by_gregorian(&searched_list, &date, &style);
+            assert_eq!(r_dates, vec!["令和6年2月28日".to_string()]);
+        }
+    }
+
+    mod represent_by_julian {
+        use crate::span::Span;
+
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            let file_span = NaiveDateSpan::new(
+                NaiveDate::from_ymd_opt(1573, 9, 4).unwrap(),
+                NaiveDate::from_ymd_opt(1868, 10, 23).unwrap(),
+            );
+            let case = Span::new(
+                "天正".to_string(),
+                NaiveDate::from_ymd_opt(1573, 9, 4).unwrap(),
+                NaiveDate::from_ymd_opt(1593, 1, 10).unwrap(),
+            );
+            let searched_list = vec![(&file_span, 'a', vec![&case])];
+
+            // グレゴリオ暦の1582-10-14はユリウス暦の1582-10-04
+            let date = NaiveDate::from_ymd_opt(1582, 10, 14).unwrap();
+            let (r_dates, r_spans) = represent_by_julian(&searched_list, &date, &Style::default());
+            assert_eq!(r_dates, vec!["天正10年10月4日".to_string()]);
+            assert_eq!(r_spans, vec!["1573-09-04_1868-10-23_a".to_string()]);
+
+            // 始期(ユリウス暦1573-08-25)の年が元年になる
+            let date = NaiveDate::from_ymd_opt(1573, 9, 4).unwrap();
+            let style = Style {
+                layout: Layout::Slashed,
+                ..Default::default()
+            };
+            let (r_dates, _r_spans) = represent_by_julian(&searched_list, &date, &style);
+            assert_eq!(r_dates, vec!["天正1/8/25".to_string()]);
+        }
+
+        #[test]
+        fn checking_value_last_day() {
+            let file_span = NaiveDateSpan::new(
+                NaiveDate::from_ymd_opt(1500, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(1600, 1, 1).unwrap(),
+            );
+            let case = Span::new(
+                "a".to_string(),
+                NaiveDate::from_ymd_opt(1500, 3, 1).unwrap(),
+                NaiveDate::from_ymd_opt(1600, 1, 1).unwrap(),
+            );
+            let searched_list = vec![(&file_span, 'a', vec![&case])];
+            let style = Style {
+                use_day_names: true,
+                ..Default::default()
+            };
+
+            // ユリウス暦の1500-02-29(グレゴリオ暦にはない閏日)
+            let date = NaiveDate::from_ymd_opt(1500, 3, 10).unwrap();
+            let (r_dates, _r_spans) = represent_by_julian(&searched_list, &date, &style);
+            assert_eq!(r_dates, vec!["a元年2月晦日".to_string()]);
+        }
+    }
+
+    mod represent_by_year_count {
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            let counts = [YearCount::Kouki, YearCount::Butsureki, YearCount::Seireki];
+            let date = NaiveDate::from_ymd_opt(2023, 10, 1).unwrap();
+
+            assert_eq!(
+                represent_by_year_count(&counts, &date, Calendar::Gregorian, &Style::default()),
+                vec![
+                    "皇紀2683年10月1日".to_string(),
+                    "仏暦2566年10月1日".to_string(),
+                    "2023年10月1日".to_string(),
+                ]
+            );
+            assert_eq!(
+                represent_by_year_count(&[], &date, Calendar::Gregorian, &Style::default()),
+                Vec::<String>::new()
+            );
+        }
+
+        #[test]
+        fn checking_value_with_style() {
+            let date = NaiveDate::from_ymd_opt(2023, 10, 1).unwrap();
+            let style = Style {
+                number: NumberStyle::Kansuuji,
+                name: NameStyle::Romaji,
+                ..Default::default()
+            };
+            assert_eq!(
+                represent_by_year_count(&[YearCount::Kouki], &date, Calendar::Gregorian, &style),
+                vec!["Koki 2683, Oct 1".to_string()]
+            );
+
+            let style = Style {
+                layout: Layout::Dotted,
+                ..Default::default()
+            };
+            assert_eq!(
+                represent_by_year_count(&[YearCount::Seireki], &date, Calendar::Gregorian, &style),
+                vec!["2023.10.1".to_string()]
+            );
+        }
+
+        #[test]
+        fn checking_value_year_one_and_before() {
+            let counts = [YearCount::Seireki, YearCount::Kouki];
+            let cases = [
+                ((1, 1, 1), ["1年1月1日", "皇紀661年1月1日"]),
+                // 0年は紀元前1年
+                ((0, 1, 1), ["紀元前1年1月1日", "皇紀660年1月1日"]),
+                ((-1, 1, 1), ["紀元前2年1月1日", "皇紀659年1月1日"]),
+                ((-660, 1, 1), ["紀元前661年1月1日", "皇紀0年1月1日"]),
+                ((-661, 1, 1), ["紀元前662年1月1日", "皇紀-1年1月1日"]),
+            ];
+            for ((y, m, d), expected) in cases.iter() {
+                let date = NaiveDate::from_ymd_opt(*y, *m, *d).unwrap();
+                assert_eq!(
+                    represent_by_year_count(&counts, &date, Calendar::Gregorian, &Style::default()),
+                    expected.to_vec(),
+                    "{}",
+                    y
+                );
+            }
+
+            let date = NaiveDate::from_ymd_opt(1, 1, 1).unwrap();
+            let style = Style {
+                number: NumberStyle::Kansuuji,
+                pattern: Some("%G%y年".to_string()),
+                ..Default::default()
+            };
+            assert_eq!(
+                represent_by_year_count(&[YearCount::Seireki], &date, Calendar::Gregorian, &style),
+                vec!["一年".to_string()]
+            );
+            // 皇紀0年・皇紀-1年を漢数字で表す
+            let style = Style {
+                number: NumberStyle::Kansuuji,
+                ..Default::default()
+            };
+            let date = NaiveDate::from_ymd_opt(-660, 1, 1).unwrap();
+            assert_eq!(
+                represent_by_year_count(&[YearCount::Kouki], &date, Calendar::Gregorian, &style),
+                vec!["皇紀〇年一月一日".to_string()]
+            );
+            let date = NaiveDate::from_ymd_opt(-760, 1, 1).unwrap();
+            assert_eq!(
+                represent_by_year_count(&[YearCount::Kouki], &date, Calendar::Gregorian, &style),
+                vec!["皇紀マイナス百年一月一日".to_string()]
+            );
+            let style = Style {
+                name: NameStyle::Romaji,
+                ..Default::default()
+            };
+            let date = NaiveDate::from_ymd_opt(-1, 1, 1).unwrap();
+            assert_eq!(
+                represent_by_year_count(&[YearCount::Seireki], &date, Calendar::Gregorian, &style),
+                vec!["BC 2, Jan 1".to_string()]
+            );
+        }
+
+        #[test]
+        fn checking_value_when_out_of_lunisolar_range() {
+            // 旧暦を計算できない日付はグレゴリオ暦の年で数える
+            assert_eq!(
+                represent_by_year_count(
+                    &[YearCount::Seireki],
+                    &NaiveDate::MIN,
+                    Calendar::Lunisolar,
+                    &Style::default()
+                ),
+                vec!["紀元前262144年1月1日".to_string()]
+            );
+        }
+
+        #[test]
+        fn checking_value_lunisolar() {
+            // 旧暦の元禄15年12月14日
+            let date = NaiveDate::from_ymd_opt(1703, 1, 30).unwrap();
+            assert_eq!(
+                represent_by_year_count(
+                    &[YearCount::Kouki],
+                    &date,
+                    Calendar::Lunisolar,
+                    &Style::default()
+                ),
+                vec!["皇紀2362年12月14日".to_string()]
+            );
+            // 改暦後はグレゴリオ暦で表す
+            let date = NaiveDate::from_ymd_opt(2023, 10, 1).unwrap();
+            assert_eq!(
+                represent_by_year_count(
+                    &[YearCount::Kouki],
+                    &date,
+                    Calendar::Lunisolar,
+                    &Style::default()
+                ),
+                vec!["皇紀2683年10月1日".to_string()]
+            );
+        }
+    }
+
+    mod represent_reign {
+        use crate::span::Span;
+
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            let start = NaiveDate::from_ymd_opt(1336, 9, 20).unwrap();
+            let end = NaiveDate::from_ymd_opt(1339, 9, 18).unwrap();
+            let case_a = Span::new("後醍醐天皇".to_string(), start, end)
+                .with_attributes(vec![("reign".to_string(), "96".to_string())]);
+            let case_b = Span::new("光明天皇".to_string(), start, end);
+
+            assert_eq!(represent_reign(&[&case_a]), "第96代後醍醐天皇");
+            assert_eq!(
+                represent_reign(&[&case_a, &case_b]),
+                "第96代後醍醐天皇|光明天皇"
+            );
+            assert_eq!(represent_reign(&[]), "");
+        }
+    }
+
     mod represent_span {
         use super::*;
 
@@ -184,6 +1291,7 @@ mod tests {
 
         #[test]
         fn checking_value() {
+            assert_eq!(to_kansuuji(0), "〇");
             assert_eq!(to_kansuuji(1), "一");
             assert_eq!(to_kansuuji(9), "九");
             assert_eq!(to_kansuuji(10), "十");
@@ -202,4 +1310,39 @@ mod tests {
             assert_eq!(to_kansuuji(1_8000_4423), "一億八千万四千四百二十三");
         }
     }
+
+    mod represent_year {
+        use super::*;
+
+        #[test]
+        fn checking_value_when_zero() {
+            assert_eq!(represent_year(0, &NumberStyle::HalfWidth), "0");
+            assert_eq!(represent_year(0, &NumberStyle::FullWidth), "０");
+            assert_eq!(represent_year(0, &NumberStyle::Kansuuji), "〇");
+            assert_eq!(represent_year(0, &NumberStyle::PositionalKansuuji), "〇");
+        }
+
+        #[test]
+        fn checking_value_when_negative() {
+            assert_eq!(represent_year(-100, &NumberStyle::HalfWidth), "-100");
+            assert_eq!(represent_year(-100, &NumberStyle::FullWidth), "－１００");
+            assert_eq!(represent_year(-100, &NumberStyle::Kansuuji), "マイナス百");
+            assert_eq!(
+                represent_year(-100, &NumberStyle::PositionalKansuuji),
+                "マイナス一〇〇"
+            );
+        }
+    }
+
+    mod to_positional_kansuuji {
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            assert_eq!(to_positional_kansuuji(0), "〇");
+            assert_eq!(to_positional_kansuuji(5), "五");
+            assert_eq!(to_positional_kansuuji(10), "一〇");
+            assert_eq!(to_positional_kansuuji(2023), "二〇二三");
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::chars::multi_byte_to_wide_char;
+use crate::chars::{decode, CharsetError};
 
 #[derive(PartialEq, Debug, Clone)]
 pub enum SaoriVersion {
@@ -61,12 +61,12 @@ impl SaoriCharset {
         }
     }
 
-    pub fn codepage(&self) -> u32 {
+    pub fn encoding(&self) -> &'static encoding_rs::Encoding {
         match self {
-            SaoriCharset::ShiftJIS => 932,
-            SaoriCharset::EucJP => 20932,
-            SaoriCharset::UTF8 => 65001,
-            SaoriCharset::ISO2022JP => 50222,
+            SaoriCharset::ShiftJIS => encoding_rs::SHIFT_JIS,
+            SaoriCharset::EucJP => encoding_rs::EUC_JP,
+            SaoriCharset::UTF8 => encoding_rs::UTF_8,
+            SaoriCharset::ISO2022JP => encoding_rs::ISO_2022_JP,
         }
     }
 }
@@ -98,7 +98,7 @@ impl From<SaoriRequestArgumentError> for SaoriRequestError {
 
 #[derive(Debug, PartialEq)]
 pub enum SaoriRequestCharsetError {
-    DecodeFailed,
+    DecodeFailed(CharsetError),
 }
 
 #[derive(Debug, PartialEq)]
@@ -125,6 +125,18 @@ pub struct SaoriRequest {
 }
 
 impl SaoriRequest {
+    /// SAORIを介さずに呼び出すときのリクエストを生成する
+    pub fn new(command: SaoriCommand, argument: Vec<String>) -> SaoriRequest {
+        SaoriRequest {
+            version: SaoriVersion::V1_0,
+            command,
+            security_level: Some(SaoriSecurityLevel::Local),
+            argument,
+            charset: SaoriCharset::UTF8,
+            sender: None,
+        }
+    }
+
     pub fn from_u8(from: &[u8]) -> Result<SaoriRequest, SaoriRequestError> {
         let (body, charset) = SaoriRequest::decode_u8(from)?;
 
@@ -201,12 +213,9 @@ impl SaoriRequest {
             }
         }
 
-        let wide_chars = multi_byte_to_wide_char(from, charset.codepage())
-            .map_err(|_| SaoriRequestCharsetError::DecodeFailed)?;
+        let body = decode(from, &charset).map_err(SaoriRequestCharsetError::DecodeFailed)?;
 
-        let p = wide_chars.partition_point(|v| *v != 0);
-
-        Ok((String::from_utf16_lossy(&wide_chars[..p]), charset))
+        Ok((body, charset))
     }
 
     /// リクエスト中のバージョン・コマンドを処理する関数。
@@ -302,8 +311,6 @@ mod tests {
         mod from_u8 {
             use super::*;
 
-            use encoding_rs;
-
             #[test]
             fn success_when_valid_request() {
                 let case = "EXECUTE SAORI/1.0\r\n
@@ -324,6 +331,25 @@ Argument0: 零\r\n
                 assert_eq!(SaoriRequest::from_u8(&case_bytes), Ok(expect));
             }
 
+            #[test]
+            fn success_when_valid_request_with_utf8() {
+                let case = "EXECUTE SAORI/1.0\r\n
+SecurityLevel: Local\r\n
+Charset: UTF-8\r\n
+Argument0: 零\r\n
+\r\n\0
+";
+                let expect = SaoriRequest {
+                    version: SaoriVersion::V1_0,
+                    command: SaoriCommand::Execute,
+                    security_level: Some(SaoriSecurityLevel::Local),
+                    argument: vec![String::from("零")],
+                    charset: SaoriCharset::UTF8,
+                    sender: None,
+                };
+                assert_eq!(SaoriRequest::from_u8(case.as_bytes()), Ok(expect));
+            }
+
             #[test]
             fn failed_when_invalid_request() {
                 let case = "SAORI/1.0\r\n
@@ -342,8 +368,6 @@ Argument0: 123\r\n
         mod decode_u8 {
             use super::*;
 
-            use encoding_rs;
-
             #[test]
             fn success_when_valid_bytes() {
                 let case = "EXECUTE SAORI/1.0\r\nCharset: Shift_JIS\r\nArgument0: 一\r\n\r\n";
@@ -355,6 +379,32 @@ Argument0: 123\r\n
                     Ok((case.to_string(), SaoriCharset::ShiftJIS))
                 );
             }
+
+            #[test]
+            fn success_when_valid_bytes_with_utf8() {
+                let case = "EXECUTE SAORI/1.0\r\nCharset: UTF-8\r\nArgument0: 一\r\n\r\n";
+                let case_string = format!("{}\0", case);
+
+                assert_eq!(
+                    SaoriRequest::decode_u8(case_string.as_bytes()),
+                    Ok((case.to_string(), SaoriCharset::UTF8))
+                );
+            }
+
+            #[test]
+            fn failed_when_malformed_bytes() {
+                let case = b"EXECUTE SAORI/1.0\r\nCharset: UTF-8\r\nArgument0: \xff\r\n\r\n\0";
+
+                assert_eq!(
+                    SaoriRequest::decode_u8(case),
+                    Err(SaoriRequestCharsetError::DecodeFailed(
+                        CharsetError::Malformed {
+                            position: 46,
+                            bytes: vec![0xff]
+                        }
+                    ))
+                );
+            }
         }
 
         mod parse_version_line {
@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 use crate::{
-    chars::wide_char_to_multi_byte,
+    chars::{encode, CharsetError},
     request::{SaoriCharset, SaoriRequest, SaoriVersion},
 };
 
@@ -36,7 +36,15 @@ impl SaoriStatus {
 
 #[derive(PartialEq, Debug)]
 pub enum SaoriResponseError {
-    DecodeFailed,
+    EncodeFailed(CharsetError),
+}
+
+impl Display for SaoriResponseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaoriResponseError::EncodeFailed(e) => write!(f, "failed to encode response: {}", e),
+        }
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -60,6 +68,19 @@ impl SaoriResponse {
         }
     }
 
+    /// status がInternal Server Error で、Resultに`error`の内容を入れた自身を生成する。
+    /// 必ずエンコードできるよう、文字コードはUTF-8にする
+    #[allow(dead_code)]
+    pub fn new_internal_server_error(&self, error: &SaoriResponseError) -> SaoriResponse {
+        SaoriResponse {
+            version: self.version.clone(),
+            status: SaoriStatus::InternalServerError,
+            result: error.to_string(),
+            value: Vec::new(),
+            charset: SaoriCharset::UTF8,
+        }
+    }
+
     /// リクエストから自身を生成する
     pub fn from_request(request: &SaoriRequest) -> SaoriResponse {
         SaoriResponse {
@@ -121,12 +142,9 @@ impl SaoriResponse {
     pub fn to_encoded_bytes(&self) -> Result<Vec<i8>, SaoriResponseError> {
         let req = self.to_string();
 
-        let mut wide_chars: Vec<u16> = req.encode_utf16().collect();
+        let result = encode(&req, &self.charset).map_err(SaoriResponseError::EncodeFailed)?;
 
-        let result = wide_char_to_multi_byte(&mut wide_chars, self.charset.codepage())
-            .map_err(|_| SaoriResponseError::DecodeFailed)?;
-
-        Ok(result)
+        Ok(result.iter().map(|v| *v as i8).collect())
     }
 }
 
@@ -139,20 +157,109 @@ impl Display for SaoriResponse {
             self.status.to_str(),
             self.charset.to_str()
         );
-        let statuses = if self.status == SaoriStatus::OK {
-            let mut result = String::new();
-            if !self.result.is_empty() {
-                result.push_str(&format!("Result: {}\r\n", self.result));
-            }
+        let statuses = match self.status {
+            SaoriStatus::OK => {
+                let mut result = String::new();
+                if !self.result.is_empty() {
+                    result.push_str(&format!("Result: {}\r\n", self.result));
+                }
 
-            for (index, value) in self.value.iter().enumerate() {
-                result.push_str(&format!("Value{}: {}\r\n", index, value));
+                for (index, value) in self.value.iter().enumerate() {
+                    result.push_str(&format!("Value{}: {}\r\n", index, value));
+                }
+                result
             }
-            result
-        } else {
-            String::new()
+            // 失敗理由
+            SaoriStatus::InternalServerError if !self.result.is_empty() => {
+                format!("Result: {}\r\n", self.result)
+            }
+            _ => String::new(),
         };
 
         write!(f, "{header}{statuses}\r\n\0")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod saori_response {
+        use super::*;
+
+        mod to_encoded_bytes {
+            use super::*;
+
+            #[test]
+            fn success_when_valid_response() {
+                let mut response = SaoriResponse {
+                    version: SaoriVersion::V1_0,
+                    status: SaoriStatus::NoContent,
+                    result: String::new(),
+                    value: Vec::new(),
+                    charset: SaoriCharset::ShiftJIS,
+                };
+                response.set_result("令和5年10月1日".to_string());
+
+                let result = response.to_encoded_bytes().unwrap();
+                let result: Vec<u8> = result.iter().map(|v| *v as u8).collect();
+                let (decoded, _encoding, _is_err) = encoding_rs::SHIFT_JIS.decode(&result);
+
+                assert_eq!(
+                    decoded,
+                    "SAORI/1.0 200 OK\r\nCharset: Shift_JIS\r\nResult: 令和5年10月1日\r\n\r\n\0"
+                );
+            }
+
+            #[test]
+            fn failed_when_unmappable_character() {
+                let mut response = SaoriResponse {
+                    version: SaoriVersion::V1_0,
+                    status: SaoriStatus::NoContent,
+                    result: String::new(),
+                    value: Vec::new(),
+                    charset: SaoriCharset::ShiftJIS,
+                };
+                response.set_result("🍣".to_string());
+
+                assert_eq!(
+                    response.to_encoded_bytes(),
+                    Err(SaoriResponseError::EncodeFailed(CharsetError::Unmappable {
+                        position: 46,
+                        character: '🍣'
+                    }))
+                );
+            }
+        }
+
+        mod new_internal_server_error {
+            use super::*;
+
+            #[test]
+            fn success_when_encode_failed() {
+                let mut response = SaoriResponse {
+                    version: SaoriVersion::V1_0,
+                    status: SaoriStatus::NoContent,
+                    result: String::new(),
+                    value: Vec::new(),
+                    charset: SaoriCharset::ShiftJIS,
+                };
+                response.set_result("🍣".to_string());
+                let error = response.to_encoded_bytes().unwrap_err();
+
+                let result = response
+                    .new_internal_server_error(&error)
+                    .to_encoded_bytes()
+                    .unwrap();
+                let result: Vec<u8> = result.iter().map(|v| *v as u8).collect();
+
+                assert_eq!(
+                    String::from_utf8(result).unwrap(),
+                    "SAORI/1.0 500 Internal Server Error\r\nCharset: UTF-8\r\n\
+                     Result: failed to encode response: \
+                     unmappable character '🍣' (U+1F363) at char 46\r\n\r\n\0"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,557 @@
+use std::collections::BTreeSet;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::lunisolar::LunisolarDate;
+use crate::represent::{calendar_era_year, Calendar};
+use crate::search::search_by_gengou;
+use crate::span::{CountingRule, NaiveDateSpan, Span, SpanListArray};
+
+pub(crate) type ReversedList<'a> = Vec<(NaiveDate, &'a NaiveDateSpan, char, &'a Span)>;
+
+/// 元号を除いた日付部分(`5年10月1日`など)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GengouDate {
+    year: u32,
+    month: u32,
+    is_leap: bool,
+    day: u32,
+}
+
+impl GengouDate {
+    pub fn new(year: u32, month: u32, is_leap: bool, day: u32) -> GengouDate {
+        GengouDate {
+            year,
+            month,
+            is_leap,
+            day,
+        }
+    }
+
+    /// `{年}年{閏}{月}月{日}日`の書式を解析する。
+    /// 数字は半角・全角の算用数字と漢数字に対応し、年には`元`も使用できる。
+    pub fn parse(s: &str) -> Option<GengouDate> {
+        let (year_str, rest) = s.trim().split_once('年')?;
+        let (month_str, rest) = rest.split_once('月')?;
+        let (day_str, rest) = rest.split_once('日')?;
+        if !rest.trim().is_empty() {
+            return None;
+        }
+
+        let year = if year_str == "元" {
+            1
+        } else {
+            parse_number(year_str)?
+        };
+        let (month_str, is_leap) = if let Some(v) = month_str.strip_prefix('閏') {
+            (v, true)
+        } else {
+            (month_str, false)
+        };
+        let month = parse_number(month_str)?;
+        let day = parse_number(day_str)?;
+
+        if year == 0 {
+            return None;
+        }
+
+        Some(GengouDate::new(year, month, is_leap, day))
+    }
+
+    /// `span`の元号の日付として、グレゴリオ暦の日付に変換する。
+    /// 元号の範囲外になる場合はNoneを返す。
+    pub fn to_gregorian(
+        &self,
+        span: &Span,
+        is_lunisolar: bool,
+        should_search_future: bool,
+    ) -> Option<NaiveDate> {
+        // 旧暦の踰年改元では始期の翌年も元年になるので、後の年も候補にする
+        let is_yunen = is_lunisolar && span.counting_rule() == CountingRule::Yunen;
+        let offsets = if is_yunen { 0..=1 } else { 0..=0 };
+
+        let now = chrono::Local::now().date_naive();
+        let start = span.span().start();
+        let end = span.span().end();
+        offsets
+            .filter_map(|offset| self.to_gregorian_with_offset(start, offset, is_lunisolar))
+            .filter(|v| {
+                !is_yunen
+                    || i32::try_from(self.year)
+                        .is_ok_and(|y| calendar_era_year(span, v, Calendar::Lunisolar) == y)
+            })
+            .find(|v| v >= start && (v <= end || (should_search_future && end >= &now)))
+    }
+
+    /// 始期の年から数えた日付。`offset`の分だけ年を後にずらす
+    fn to_gregorian_with_offset(
+        &self,
+        start: &NaiveDate,
+        offset: i32,
+        is_lunisolar: bool,
+    ) -> Option<NaiveDate> {
+        // 年が大きすぎるときは変換しない
+        let year = i32::try_from(self.year)
+            .ok()?
+            .checked_sub(1)?
+            .checked_add(offset)?;
+        let gregorian = if self.is_leap {
+            None
+        } else {
+            NaiveDate::from_ymd_opt(start.year().checked_add(year)?, self.month, self.day)
+        };
+
+        if is_lunisolar {
+            let start_year =
+                LunisolarDate::from_gregorian(start).map_or(start.year(), |v| v.year());
+            LunisolarDate::new(
+                start_year.checked_add(year)?,
+                self.month,
+                self.is_leap,
+                self.day,
+            )
+            .to_gregorian()
+            // 改暦以後はグレゴリオ暦
+            .or(gregorian.filter(|v| LunisolarDate::from_gregorian(v).is_none()))
+        } else {
+            gregorian
+        }
+    }
+}
+
+/// 元号を使用した日付から、グレゴリオ暦の日付の候補を全て探す
+pub(crate) fn reverse_from_array<'a>(
+    spans_array: &'a SpanListArray,
+    s: &str,
+    selector: &[&str],
+    is_lunisolar: bool,
+    should_search_future: bool,
+) -> ReversedList<'a> {
+    let s = s.trim();
+    let mut result: ReversedList<'a> = Vec::new();
+
+    let gengou_list: BTreeSet<&str> = spans_array
+        .iter()
+        .flat_map(|(_, spans_map)| spans_map.values())
+        .flat_map(|l| l.spans().iter())
+        .flat_map(|v| std::iter::once(v.gengou()).chain(v.variants().iter().map(|v| v.as_str())))
+        .filter(|v| !v.is_empty() && s.starts_with(v))
+        .collect();
+
+    for gengou in gengou_list {
+        let gengou_date = if let Some(v) = GengouDate::parse(&s[gengou.len()..]) {
+            v
+        } else {
+            continue;
+        };
+
+        for (file_span, initial, spans) in search_by_gengou(spans_array, gengou, selector) {
+            for span in spans {
+                if let Some(date) =
+                    gengou_date.to_gregorian(span, is_lunisolar, should_search_future)
+                {
+                    result.push((date, file_span, initial, span));
+                }
+            }
+        }
+    }
+
+    result.sort_by(|a, b| match a.1.cmp(b.1) {
+        std::cmp::Ordering::Equal => a.2.cmp(&b.2),
+        o => o,
+    });
+
+    result
+}
+
+/// 算用数字(半角・全角)または漢数字を数値にする
+pub(crate) fn parse_number(s: &str) -> Option<u32> {
+    let s = s.trim();
+    if s.is_empty() {
+        return None;
+    }
+
+    if s.chars().all(|c| to_digit(c).is_some()) {
+        return s.chars().try_fold(0u32, |acc, c| {
+            acc.checked_mul(10)?.checked_add(to_digit(c)?)
+        });
+    }
+
+    let mut total = 0u32;
+    let mut section = 0u32;
+    let mut digit: Option<u32> = None;
+
+    for c in s.chars() {
+        if let Some(d) = to_kansuuji_digit(c) {
+            // 二〇二三のような位取りの表記
+            digit = Some(digit.map_or(Some(d), |v| v.checked_mul(10)?.checked_add(d))?);
+        } else if let Some(unit) = to_kansuuji_unit(c) {
+            section = section.checked_add(digit.unwrap_or(1).checked_mul(unit)?)?;
+            digit = None;
+        } else if c == '万' {
+            let value = section.checked_add(digit.unwrap_or(0))?;
+            total = total.checked_add(value.max(1).checked_mul(10000)?)?;
+            section = 0;
+            digit = None;
+        } else {
+            return None;
+        }
+    }
+
+    total.checked_add(section)?.checked_add(digit.unwrap_or(0))
+}
+
+fn to_digit(c: char) -> Option<u32> {
+    match c {
+        '0'..='9' => c.to_digit(10),
+        '０'..='９' => Some(c as u32 - '０' as u32),
+        _ => None,
+    }
+}
+
+fn to_kansuuji_digit(c: char) -> Option<u32> {
+    match c {
+        '〇' | '零' => Some(0),
+        '一' => Some(1),
+        '二' => Some(2),
+        '三' => Some(3),
+        '四' => Some(4),
+        '五' => Some(5),
+        '六' => Some(6),
+        '七' => Some(7),
+        '八' => Some(8),
+        '九' => Some(9),
+        _ => None,
+    }
+}
+
+fn to_kansuuji_unit(c: char) -> Option<u32> {
+    match c {
+        '十' => Some(10),
+        '百' => Some(100),
+        '千' => Some(1000),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod gengou_date {
+        use super::*;
+
+        mod parse {
+            use super::*;
+
+            #[test]
+            fn success_when_arabic_numerals() {
+                assert_eq!(
+                    GengouDate::parse("5年10月1日"),
+                    Some(GengouDate::new(5, 10, false, 1))
+                );
+                assert_eq!(
+                    GengouDate::parse("５年１０月１日"),
+                    Some(GengouDate::new(5, 10, false, 1))
+                );
+            }
+
+            #[test]
+            fn success_when_kansuuji() {
+                assert_eq!(
+                    GengouDate::parse("十五年十二月十四日"),
+                    Some(GengouDate::new(15, 12, false, 14))
+                );
+            }
+
+            #[test]
+            fn success_when_first_year_and_leap_month() {
+                assert_eq!(
+                    GengouDate::parse("元年閏5月9日"),
+                    Some(GengouDate::new(1, 5, true, 9))
+                );
+            }
+
+            #[test]
+            fn failed_when_invalid_format() {
+                assert_eq!(GengouDate::parse("5年10月"), None);
+                assert_eq!(GengouDate::parse("5年10月1日です"), None);
+                assert_eq!(GengouDate::parse("0年10月1日"), None);
+                assert_eq!(GengouDate::parse("a年10月1日"), None);
+            }
+        }
+
+        mod to_gregorian {
+            use super::*;
+
+            #[test]
+            fn success_when_gregorian() {
+                let span = Span::new(
+                    "元禄".to_string(),
+                    NaiveDate::from_ymd_opt(1688, 10, 23).unwrap(),
+                    NaiveDate::from_ymd_opt(1704, 4, 16).unwrap(),
+                );
+                let case = GengouDate::new(15, 12, false, 14);
+
+                assert_eq!(
+                    case.to_gregorian(&span, false, true),
+                    NaiveDate::from_ymd_opt(1702, 12, 14)
+                );
+            }
+
+            #[test]
+            fn success_when_lunisolar() {
+                let span = Span::new(
+                    "元禄".to_string(),
+                    NaiveDate::from_ymd_opt(1688, 10, 23).unwrap(),
+                    NaiveDate::from_ymd_opt(1704, 4, 16).unwrap(),
+                );
+                let case = GengouDate::new(15, 12, false, 14);
+
+                assert_eq!(
+                    case.to_gregorian(&span, true, true),
+                    NaiveDate::from_ymd_opt(1703, 1, 30)
+                );
+            }
+
+            #[test]
+            fn success_when_counting_rule() {
+                let span = |rule: &str| {
+                    Span::new(
+                        "元禄".to_string(),
+                        NaiveDate::from_ymd_opt(1688, 10, 23).unwrap(),
+                        NaiveDate::from_ymd_opt(1704, 4, 16).unwrap(),
+                    )
+                    .with_attributes(vec![("rule".to_string(), rule.to_string())])
+                };
+
+                // グレゴリオ暦では数え方によらず暦の年で数える
+                assert_eq!(
+                    GengouDate::new(15, 1, false, 10).to_gregorian(&span("ritsunen"), false, true),
+                    NaiveDate::from_ymd_opt(1702, 1, 10)
+                );
+                assert_eq!(
+                    GengouDate::new(16, 1, false, 10).to_gregorian(&span("yunen"), false, true),
+                    NaiveDate::from_ymd_opt(1703, 1, 10)
+                );
+                assert_eq!(
+                    GengouDate::new(15, 12, false, 14).to_gregorian(&span("ritsunen"), true, true),
+                    NaiveDate::from_ymd_opt(1703, 1, 30)
+                );
+                assert_eq!(
+                    GengouDate::new(14, 12, false, 14).to_gregorian(&span("yunen"), true, true),
+                    NaiveDate::from_ymd_opt(1703, 1, 30)
+                );
+                // 踰年改元では、始期から翌年の正月までも元年
+                assert_eq!(
+                    GengouDate::new(1, 11, false, 9).to_gregorian(&span("yunen"), true, true),
+                    NaiveDate::from_ymd_opt(1688, 12, 1)
+                );
+                assert_eq!(
+                    GengouDate::new(1, 1, false, 1).to_gregorian(&span("yunen"), true, true),
+                    NaiveDate::from_ymd_opt(1689, 2, 20)
+                );
+            }
+
+            #[test]
+            fn return_none_when_year_overflows() {
+                let span = Span::new(
+                    "令和".to_string(),
+                    NaiveDate::from_ymd_opt(2019, 5, 1).unwrap(),
+                    NaiveDate::from_ymd_opt(2100, 1, 1).unwrap(),
+                );
+
+                // i32に収まらない年
+                let case = GengouDate::new(2147483648, 1, false, 1);
+                assert_eq!(case.to_gregorian(&span, false, true), None);
+                assert_eq!(case.to_gregorian(&span, true, true), None);
+                // 始期の年を足すと溢れる年
+                let case = GengouDate::new(2147483647, 1, false, 1);
+                assert_eq!(case.to_gregorian(&span, false, true), None);
+                assert_eq!(case.to_gregorian(&span, true, true), None);
+            }
+
+            #[test]
+            fn success_when_lunisolar_after_gregorian_adoption() {
+                let span = Span::new(
+                    "明治".to_string(),
+                    NaiveDate::from_ymd_opt(1868, 1, 25).unwrap(),
+                    NaiveDate::from_ymd_opt(1912, 7, 29).unwrap(),
+                );
+                let case = GengouDate::new(6, 1, false, 1);
+
+                assert_eq!(
+                    case.to_gregorian(&span, true, true),
+                    NaiveDate::from_ymd_opt(1873, 1, 1)
+                );
+            }
+
+            #[test]
+            fn return_none_when_out_of_span() {
+                let span = Span::new(
+                    "令和".to_string(),
+                    NaiveDate::from_ymd_opt(2019, 5, 1).unwrap(),
+                    NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+                );
+
+                let case = GengouDate::new(1, 4, false, 30);
+                assert_eq!(case.to_gregorian(&span, false, true), None);
+
+                let case = GengouDate::new(3, 1, false, 1);
+                assert_eq!(case.to_gregorian(&span, false, true), None);
+            }
+
+            #[test]
+            fn success_when_future_of_current_gengou() {
+                let now = chrono::Local::now().date_naive();
+                let span = Span::new(
+                    "令和".to_string(),
+                    NaiveDate::from_ymd_opt(2019, 5, 1).unwrap(),
+                    now,
+                );
+                let case = GengouDate::new(now.year() as u32 - 2019 + 2, 1, false, 1);
+                let expect = NaiveDate::from_ymd_opt(now.year() + 1, 1, 1);
+
+                assert_eq!(case.to_gregorian(&span, false, true), expect);
+                assert_eq!(case.to_gregorian(&span, false, false), None);
+            }
+        }
+    }
+
+    mod reverse_from_array {
+        use std::path::PathBuf;
+
+        use crate::span::{load_spans, SPAN_DIR_PATH};
+
+        use super::*;
+
+        #[test]
+        fn return_single_element_when_unique_gengou() {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(SPAN_DIR_PATH);
+            let array = load_spans(&path).unwrap();
+
+            let result = reverse_from_array(&array, "令和5年10月1日", &[], false, true);
+
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].0, NaiveDate::from_ymd_opt(2023, 10, 1).unwrap());
+            assert_eq!(result[0].3.gengou(), "令和");
+        }
+
+        #[test]
+        fn return_multiple_elements_when_gengou_is_in_multiple_files() {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(SPAN_DIR_PATH);
+            let array = load_spans(&path).unwrap();
+
+            let result = reverse_from_array(&array, "建武2年1月1日", &[], false, true);
+
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0].0, NaiveDate::from_ymd_opt(1335, 1, 1).unwrap());
+            assert_eq!(result[0].2, 'h');
+            assert_eq!(result[1].0, NaiveDate::from_ymd_opt(1335, 1, 1).unwrap());
+            assert_eq!(result[1].2, 'n');
+
+            let result = reverse_from_array(&array, "建武2年1月1日", &["*", "n"], false, true);
+
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].2, 'n');
+        }
+
+        #[test]
+        fn return_element_when_gengou_ends_with_numeral() {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(SPAN_DIR_PATH);
+            let array = load_spans(&path).unwrap();
+
+            let result = reverse_from_array(&array, "康元元年12月1日", &[], false, true);
+
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].0, NaiveDate::from_ymd_opt(1256, 12, 1).unwrap());
+            assert_eq!(result[0].3.gengou(), "康元");
+        }
+
+        #[test]
+        fn return_element_when_kansuuji_and_lunisolar() {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(SPAN_DIR_PATH);
+            let array = load_spans(&path).unwrap();
+
+            let result = reverse_from_array(&array, "元禄十五年十二月十四日", &[], true, true);
+
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].0, NaiveDate::from_ymd_opt(1703, 1, 30).unwrap());
+        }
+
+        #[test]
+        fn return_element_when_counting_rule() {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(SPAN_DIR_PATH);
+            let array = load_spans(&path).unwrap();
+            let expect = NaiveDate::from_ymd_opt(1869, 1, 15).unwrap();
+
+            // 明治の数え方(立年改元)は旧暦で表すときのみ使用する
+            let result = reverse_from_array(&array, "明治2年1月15日", &[], false, true);
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].0, expect);
+            assert!(reverse_from_array(&array, "明治元年1月15日", &[], false, true).is_empty());
+
+            let result = reverse_from_array(&array, "明治元年12月3日", &[], true, true);
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].0, expect);
+        }
+
+        #[test]
+        fn return_element_when_variant_of_bundled_list() {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(SPAN_DIR_PATH);
+            let array = load_spans(&path).unwrap();
+
+            let result = reverse_from_array(&array, "慶應4年1月1日", &[], true, true);
+
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].0, NaiveDate::from_ymd_opt(1868, 1, 25).unwrap());
+            assert_eq!(result[0].3.gengou(), "慶応");
+        }
+
+        #[test]
+        fn return_nothing_when_year_is_too_large() {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(SPAN_DIR_PATH);
+            let array = load_spans(&path).unwrap();
+
+            for s in ["令和2147483648年1月1日", "令和2147483647年1月1日"] {
+                assert!(
+                    reverse_from_array(&array, s, &[], false, true).is_empty(),
+                    "{}",
+                    s
+                );
+            }
+        }
+
+        #[test]
+        fn return_nothing_when_unknown_gengou() {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(SPAN_DIR_PATH);
+            let array = load_spans(&path).unwrap();
+
+            assert!(reverse_from_array(&array, "未知5年1月1日", &[], false, true).is_empty());
+        }
+    }
+
+    mod parse_number {
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            assert_eq!(parse_number("15"), Some(15));
+            assert_eq!(parse_number("１５"), Some(15));
+            assert_eq!(parse_number("十"), Some(10));
+            assert_eq!(parse_number("十五"), Some(15));
+            assert_eq!(parse_number("二十一"), Some(21));
+            assert_eq!(parse_number("九百九十九"), Some(999));
+            assert_eq!(parse_number("一万二十"), Some(10020));
+            assert_eq!(parse_number("二〇二三"), Some(2023));
+            assert_eq!(parse_number("一〇"), Some(10));
+        }
+
+        #[test]
+        fn return_none_when_invalid_str() {
+            assert_eq!(parse_number(""), None);
+            assert_eq!(parse_number("1a"), None);
+            assert_eq!(parse_number("元"), None);
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use chrono::NaiveDate;
+use std::fmt;
+
+use chrono::{DateTime, FixedOffset, NaiveDate};
 
 use crate::span::{NaiveDateSpan, Span, SpanListArray};
 
@@ -22,20 +24,445 @@ pub(crate) fn search_from_array<'a>(
 
             for (i, l) in span_list {
                 let searched_list = search_from_list(l.spans(), date);
-                let position = result
-                    .binary_search_by(|(d, c, _): &(&NaiveDateSpan, char, _)| match d.cmp(&span) {
-                        std::cmp::Ordering::Equal => c.cmp(i),
-                        o => o,
-                    })
-                    .unwrap_or_else(|v| v);
-                result.insert(position, (span, *i, searched_list));
+                insert_sorted(&mut result, span, *i, searched_list);
+            }
+        }
+    }
+
+    result
+}
+
+/// `instant`を含む`Span`を探す。時刻付きの始期・終期を考慮する。
+/// 日付の範囲によらず、全てのファイルの全ての`Span`から探す
+pub(crate) fn search_from_array_at<'a>(
+    spans_array: &'a SpanListArray,
+    instant: &DateTime<FixedOffset>,
+    selector: &[&str],
+) -> SearchedSpanList<'a> {
+    let mut result = Vec::new();
+
+    let mut selector = selector.iter();
+    for (span, spans_map) in spans_array.iter() {
+        let s = selector.next().unwrap_or(&"*");
+
+        let span_list = spans_map
+            .iter()
+            .filter(|v| s.contains('*') || s.contains(*v.0) || s.is_empty());
+
+        for (i, l) in span_list {
+            let searched_list: Vec<&Span> = l
+                .spans()
+                .iter()
+                .filter(|v| v.contains_instant(instant))
+                .collect();
+            if searched_list.is_empty() {
+                continue;
             }
+
+            insert_sorted(&mut result, span, *i, searched_list);
         }
     }
 
     result
 }
 
+/// `date`(`instant`が指定されていればその時点)を含む`Span`を探す。
+/// `initial`と同じ頭文字のファイルを優先し、なければ全てのファイルから探す。
+/// 元号の一覧と同じ範囲分けをした別の一覧(天皇など)から、元号に対応するものを探すのに使う。
+pub(crate) fn search_by_initial<'a>(
+    spans_array: &'a SpanListArray,
+    date: &NaiveDate,
+    instant: Option<&DateTime<FixedOffset>>,
+    initial: char,
+) -> Vec<&'a Span> {
+    let searched_list = match instant {
+        Some(i) => search_from_array_at(spans_array, i, &[]),
+        None => search_from_array(spans_array, date, &[]),
+    };
+    let has_same_initial = searched_list.iter().any(|(_, c, _)| *c == initial);
+
+    searched_list
+        .into_iter()
+        .filter(|(_, c, _)| !has_same_initial || *c == initial)
+        .flat_map(|(_, _, spans)| spans)
+        .collect()
+}
+
+/// いずれかのファイルの範囲に`date`が含まれるかどうか。
+/// 一覧のない期間と、一覧はあるがあてはまるものがない期間を区別するのに使う。
+pub(crate) fn is_covered(spans_array: &SpanListArray, date: &NaiveDate) -> bool {
+    spans_array
+        .iter()
+        .any(|(span, _)| span.start() <= date && span.end() >= date)
+}
+
+/// 元号名(異表記を含む)が一致する`Span`を全てのファイルから探す
+pub(crate) fn search_by_gengou<'a>(
+    spans_array: &'a SpanListArray,
+    gengou: &str,
+    selector: &[&str],
+) -> SearchedSpanList<'a> {
+    let mut result = Vec::new();
+    // 元号のない期間は探さない
+    if gengou.is_empty() {
+        return result;
+    }
+
+    let mut selector = selector.iter();
+    for (span, spans_map) in spans_array.iter() {
+        let s = selector.next().unwrap_or(&"*");
+
+        let span_list = spans_map
+            .iter()
+            .filter(|v| s.contains('*') || s.contains(*v.0) || s.is_empty());
+
+        for (i, l) in span_list {
+            let searched_list: Vec<&Span> =
+                l.spans().iter().filter(|v| v.is_named(gengou)).collect();
+            if searched_list.is_empty() {
+                continue;
+            }
+
+            insert_sorted(&mut result, span, *i, searched_list);
+        }
+    }
+
+    result
+}
+
+/// `range`と重なる`Span`を、ファイルの範囲、頭文字、始期の順に探す。
+/// 元号のない期間も含む
+pub(crate) fn search_range_from_array<'a>(
+    spans_array: &'a SpanListArray,
+    range: &NaiveDateSpan,
+    selector: &[&str],
+) -> SearchedSpanList<'a> {
+    let mut result = Vec::new();
+
+    let mut selector = selector.iter();
+    for (span, spans_map) in spans_array.iter() {
+        let s = selector.next().unwrap_or(&"*");
+
+        if span.intersection(range).is_none() {
+            continue;
+        }
+
+        let span_list = spans_map
+            .iter()
+            .filter(|v| s.contains('*') || s.contains(*v.0) || s.is_empty());
+
+        for (i, l) in span_list {
+            let searched_list: Vec<&Span> = l
+                .spans()
+                .iter()
+                .filter(|v| v.span().intersection(range).is_some())
+                .collect();
+            if searched_list.is_empty() {
+                continue;
+            }
+
+            insert_sorted(&mut result, span, *i, searched_list);
+        }
+    }
+
+    result
+}
+
+/// 元号を移る向き
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Direction {
+    /// 前の元号
+    Previous,
+    /// 次の元号
+    Next,
+}
+
+/// 元号名(異表記を含む)が一致する元号の、前または次の元号を探す。元号のない期間は飛ばす。
+/// ファイルの最初(最後)の元号のときは、前(後)の範囲のファイルから、
+/// 同じ頭文字のファイルを優先して探す(なければその範囲の全てのファイルから探す)
+pub(crate) fn search_neighbor_from_array<'a>(
+    spans_array: &'a SpanListArray,
+    gengou: &str,
+    direction: Direction,
+    selector: &[&str],
+) -> SearchedSpanList<'a> {
+    let mut result = Vec::new();
+    // 元号のない期間は探さない
+    if gengou.is_empty() {
+        return result;
+    }
+
+    let selected = |index: usize| {
+        let s = selector.get(index).unwrap_or(&"*");
+        spans_array[index]
+            .1
+            .iter()
+            .filter(move |v| s.contains('*') || s.contains(*v.0) || s.is_empty())
+    };
+
+    for (index, (span, _)) in spans_array.iter().enumerate() {
+        for (i, l) in selected(index) {
+            for target in l.spans().iter().filter(|v| v.is_named(gengou)) {
+                let neighbor = match direction {
+                    Direction::Previous => l.previous(target),
+                    Direction::Next => l.next(target),
+                };
+                if let Some(v) = neighbor {
+                    insert_merged(&mut result, span, *i, v);
+                    continue;
+                }
+
+                let adjacent = match direction {
+                    Direction::Previous => index.checked_sub(1),
+                    Direction::Next => Some(index + 1).filter(|v| *v < spans_array.len()),
+                };
+                let Some(adjacent) = adjacent else {
+                    continue;
+                };
+                let has_same_initial = selected(adjacent).any(|(c, _)| c == i);
+                for (c, adjacent_list) in
+                    selected(adjacent).filter(|(c, _)| !has_same_initial || *c == i)
+                {
+                    let v = match direction {
+                        Direction::Previous => adjacent_list.last_named(),
+                        Direction::Next => adjacent_list.first_named(),
+                    };
+                    if let Some(v) = v {
+                        insert_merged(&mut result, &spans_array[adjacent].0, *c, v);
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// 同じファイルの`Span`があればまとめ、なければファイルの範囲、頭文字の順になるように挿入する
+fn insert_merged<'a>(
+    result: &mut SearchedSpanList<'a>,
+    span: &'a NaiveDateSpan,
+    initial: char,
+    target: &'a Span,
+) {
+    let position =
+        result.binary_search_by(|(d, c, _): &(&NaiveDateSpan, char, _)| match d.cmp(&span) {
+            std::cmp::Ordering::Equal => c.cmp(&initial),
+            o => o,
+        });
+    match position {
+        Ok(p) => {
+            let spans = &mut result[p].2;
+            if !spans.iter().any(|v| std::ptr::eq(*v, target)) {
+                spans.push(target);
+                spans.sort_by_key(|v| v.span().start());
+            }
+        }
+        Err(p) => result.insert(p, (span, initial, vec![target])),
+    }
+}
+
+/// 名前で探したときの一致の仕方。前のものほど近い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum NameMatch {
+    /// 元号または異表記と一致
+    Exact,
+    /// 読み(カタカナはひらがなとする)またはローマ字表記(大文字・小文字を区別しない)と一致
+    Reading,
+    /// 元号・異表記・読みの先頭と一致
+    Prefix,
+    /// 元号・異表記・読みとの編集距離が近い
+    Similar,
+}
+
+impl fmt::Display for NameMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            NameMatch::Exact => "exact",
+            NameMatch::Reading => "reading",
+            NameMatch::Prefix => "prefix",
+            NameMatch::Similar => "similar",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// 名前が`name`に近い`Span`を全てのファイルから探す。
+/// 最も近い一致の仕方のものだけを返す。元号のない期間は探さない。
+/// 編集距離で探したときは、ファイルによらず編集距離・始期の順に一つずつ並べる
+pub(crate) fn search_by_name<'a>(
+    spans_array: &'a SpanListArray,
+    name: &str,
+    selector: &[&str],
+) -> (Option<NameMatch>, SearchedSpanList<'a>) {
+    let name = to_hiragana(name.trim());
+    if name.is_empty() {
+        return (None, Vec::new());
+    }
+
+    let mut matched = Vec::new();
+    let mut selector = selector.iter();
+    for (span, spans_map) in spans_array.iter() {
+        let s = selector.next().unwrap_or(&"*");
+
+        let span_list = spans_map
+            .iter()
+            .filter(|v| s.contains('*') || s.contains(*v.0) || s.is_empty());
+
+        for (i, l) in span_list {
+            let searched_list: Vec<(NameMatch, usize, &Span)> = l
+                .spans()
+                .iter()
+                .filter_map(|v| match_name(v, &name).map(|(m, d)| (m, d, v)))
+                .collect();
+            if !searched_list.is_empty() {
+                matched.push((span, *i, searched_list));
+            }
+        }
+    }
+
+    let best = matched
+        .iter()
+        .flat_map(|(_, _, l)| l.iter().map(|(m, _, _)| *m))
+        .min();
+
+    let mut result = Vec::new();
+    if best == Some(NameMatch::Similar) {
+        let mut similar: Vec<(usize, &NaiveDateSpan, char, &Span)> = matched
+            .into_iter()
+            .flat_map(|(span, i, l)| l.into_iter().map(move |(_, d, v)| (d, span, i, v)))
+            .collect();
+        similar.sort_by(|a, b| {
+            (a.0, a.3.span().start(), a.1, a.2).cmp(&(b.0, b.3.span().start(), b.1, b.2))
+        });
+        result.extend(
+            similar
+                .into_iter()
+                .map(|(_, span, i, v)| (span, i, vec![v])),
+        );
+        return (best, result);
+    }
+
+    for (span, i, searched_list) in matched {
+        let searched_list: Vec<&Span> = searched_list
+            .into_iter()
+            .filter(|(m, _, _)| Some(*m) == best)
+            .map(|(_, _, v)| v)
+            .collect();
+        if !searched_list.is_empty() {
+            insert_sorted(&mut result, span, i, searched_list);
+        }
+    }
+
+    (best, result)
+}
+
+/// `span`の名前と`name`(ひらがなにしたもの)の一致の仕方と、編集距離(`Similar`以外は0)
+fn match_name(span: &Span, name: &str) -> Option<(NameMatch, usize)> {
+    if span.gengou().is_empty() {
+        return None;
+    }
+    if span.is_named(name) {
+        return Some((NameMatch::Exact, 0));
+    }
+
+    let reading = span.reading().map(to_hiragana);
+    if reading.as_deref() == Some(name)
+        || span.romaji().is_some_and(|v| v.eq_ignore_ascii_case(name))
+    {
+        return Some((NameMatch::Reading, 0));
+    }
+
+    let names: Vec<&str> = std::iter::once(span.gengou())
+        .chain(span.variants().iter().map(|v| v.as_str()))
+        .chain(reading.as_deref())
+        .filter(|v| !v.is_empty())
+        .collect();
+    if names.iter().any(|v| v.starts_with(name)) {
+        return Some((NameMatch::Prefix, 0));
+    }
+
+    // 許す距離は名前の長さの3分の1程度(2文字なら1、5文字なら2)とする
+    let length = name.chars().count();
+    let threshold = (length + 1) / 3;
+    names
+        .iter()
+        .map(|v| edit_distance(v, name))
+        .min()
+        .filter(|v| *v <= threshold && *v < length)
+        .map(|v| (NameMatch::Similar, v))
+}
+
+/// カタカナをひらがなにする
+fn to_hiragana(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+/// 文字単位の編集距離(レーベンシュタイン距離)
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            current.push((prev[j] + cost).min(prev[j + 1] + 1).min(current[j] + 1));
+        }
+        prev = current;
+    }
+    prev[b.len()]
+}
+
+/// 読み込んだ全ての`Span`を、ファイルの範囲、頭文字の順に返す。
+/// `index`が指定されたときは、その番号(0始まり)の範囲のファイルのみを返す
+pub(crate) fn search_all_from_array<'a>(
+    spans_array: &'a SpanListArray,
+    selector: &[&str],
+    index: Option<usize>,
+) -> SearchedSpanList<'a> {
+    let mut result = Vec::new();
+
+    let mut selector = selector.iter();
+    for (i, (span, spans_map)) in spans_array.iter().enumerate() {
+        let s = selector.next().unwrap_or(&"*");
+
+        if index.is_some_and(|v| v != i) {
+            continue;
+        }
+
+        let span_list = spans_map
+            .iter()
+            .filter(|v| s.contains('*') || s.contains(*v.0) || s.is_empty());
+
+        for (c, l) in span_list {
+            insert_sorted(&mut result, span, *c, l.spans().iter().collect());
+        }
+    }
+
+    result
+}
+
+/// ファイルの範囲、頭文字の順になるように挿入する
+fn insert_sorted<'a>(
+    result: &mut SearchedSpanList<'a>,
+    span: &'a NaiveDateSpan,
+    initial: char,
+    searched_list: Vec<&'a Span>,
+) {
+    let position = result
+        .binary_search_by(|(d, c, _): &(&NaiveDateSpan, char, _)| match d.cmp(&span) {
+            std::cmp::Ordering::Equal => c.cmp(&initial),
+            o => o,
+        })
+        .unwrap_or_else(|v| v);
+    result.insert(position, (span, initial, searched_list));
+}
+
 fn search_from_list<'a>(spans: &'a [Span], date: &NaiveDate) -> Vec<&'a Span> {
     let start_point = spans.partition_point(|t| t.span().start() < date && t.span().end() < date);
     let end_point = spans.partition_point(|t| t.span().start() <= date);
@@ -398,6 +825,531 @@ mod tests {
         }
     }
 
+    mod search_by_gengou {
+        use std::collections::HashMap;
+
+        use crate::span::{NaiveDateSpan, SpanList};
+
+        use super::*;
+
+        pub(super) fn case_array() -> SpanListArray {
+            let date_span = NaiveDateSpan::new(
+                NaiveDate::from_ymd_opt(100, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(1000, 1, 1).unwrap(),
+            );
+            let mut map = HashMap::new();
+            map.insert(
+                'a',
+                SpanList::new(
+                    date_span.clone(),
+                    'a',
+                    vec![
+                        Span::new(
+                            "a".to_string(),
+                            NaiveDate::from_ymd_opt(100, 1, 1).unwrap(),
+                            NaiveDate::from_ymd_opt(200, 1, 1).unwrap(),
+                        ),
+                        Span::new(
+                            "b".to_string(),
+                            NaiveDate::from_ymd_opt(200, 1, 1).unwrap(),
+                            NaiveDate::from_ymd_opt(1000, 1, 1).unwrap(),
+                        ),
+                    ],
+                ),
+            );
+            map.insert(
+                'b',
+                SpanList::new(
+                    date_span.clone(),
+                    'b',
+                    vec![
+                        Span::new(
+                            "a".to_string(),
+                            NaiveDate::from_ymd_opt(100, 1, 1).unwrap(),
+                            NaiveDate::from_ymd_opt(300, 1, 1).unwrap(),
+                        ),
+                        Span::new(
+                            "".to_string(),
+                            NaiveDate::from_ymd_opt(300, 1, 1).unwrap(),
+                            NaiveDate::from_ymd_opt(400, 1, 1).unwrap(),
+                        ),
+                        Span::new(
+                            "c".to_string(),
+                            NaiveDate::from_ymd_opt(400, 1, 1).unwrap(),
+                            NaiveDate::from_ymd_opt(1000, 1, 1).unwrap(),
+                        ),
+                    ],
+                ),
+            );
+
+            vec![(date_span, map)]
+        }
+
+        #[test]
+        fn return_multiple_elements_when_gengou_is_in_multiple_files() {
+            let array = case_array();
+
+            let result = search_by_gengou(&array, "a", &[]);
+
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0].1, 'a');
+            assert_eq!(
+                result[0].2[0].span().end(),
+                &NaiveDate::from_ymd_opt(200, 1, 1).unwrap()
+            );
+            assert_eq!(result[1].1, 'b');
+            assert_eq!(
+                result[1].2[0].span().end(),
+                &NaiveDate::from_ymd_opt(300, 1, 1).unwrap()
+            );
+        }
+
+        #[test]
+        fn return_single_element_when_selector_specified() {
+            let array = case_array();
+
+            let result = search_by_gengou(&array, "a", &["b"]);
+
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].1, 'b');
+        }
+
+        #[test]
+        fn return_nothing_when_no_gengou_matches() {
+            let array = case_array();
+
+            assert!(search_by_gengou(&array, "d", &[]).is_empty());
+            assert!(search_by_gengou(&array, "", &[]).is_empty());
+        }
+    }
+
+    mod search_from_array_at {
+        use std::collections::HashMap;
+
+        use chrono::TimeZone;
+
+        use crate::span::{self, SpanList};
+
+        use super::*;
+
+        fn case_array() -> SpanListArray {
+            let contents = "a,2000-01-01,2000-06-01T12:00\nb,2000-06-01T12:00,2000-12-31\n";
+            let (date_span, spans) = span::parse_contents(contents).unwrap().unwrap();
+            let mut map = HashMap::new();
+            map.insert('a', SpanList::new(date_span.clone(), 'a', spans));
+            vec![(date_span, map)]
+        }
+
+        fn gengou_list(result: &SearchedSpanList<'_>) -> Vec<String> {
+            result
+                .iter()
+                .flat_map(|(_, _, spans)| spans.iter().map(|v| v.gengou().to_string()))
+                .collect()
+        }
+
+        #[test]
+        fn return_span_containing_instant() {
+            let array = case_array();
+            let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+
+            let instant = jst.with_ymd_and_hms(2000, 6, 1, 11, 59, 59).unwrap();
+            assert_eq!(
+                gengou_list(&search_from_array_at(&array, &instant, &[])),
+                vec!["a"]
+            );
+
+            let instant = jst.with_ymd_and_hms(2000, 6, 1, 12, 0, 0).unwrap();
+            assert_eq!(
+                gengou_list(&search_from_array_at(&array, &instant, &[])),
+                vec!["b"]
+            );
+
+            let instant = FixedOffset::east_opt(0)
+                .unwrap()
+                .with_ymd_and_hms(2000, 6, 1, 2, 59, 59)
+                .unwrap();
+            assert_eq!(
+                gengou_list(&search_from_array_at(&array, &instant, &[])),
+                vec!["a"]
+            );
+
+            // 日付のみで探すときは、境界の日は次の元号の日とする
+            let date = NaiveDate::from_ymd_opt(2000, 6, 1).unwrap();
+            assert_eq!(
+                gengou_list(&search_from_array(&array, &date, &[])),
+                vec!["b"]
+            );
+        }
+
+        #[test]
+        fn return_nothing_when_no_span_contains_instant() {
+            let array = case_array();
+            let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+
+            let instant = jst.with_ymd_and_hms(2001, 1, 1, 0, 0, 0).unwrap();
+            assert!(search_from_array_at(&array, &instant, &[]).is_empty());
+            let instant = jst.with_ymd_and_hms(2000, 12, 31, 23, 59, 59).unwrap();
+            assert!(!search_from_array_at(&array, &instant, &[]).is_empty());
+            let instant = jst.with_ymd_and_hms(2000, 6, 1, 12, 0, 0).unwrap();
+            assert!(search_from_array_at(&array, &instant, &["z"]).is_empty());
+        }
+    }
+
+    mod search_by_initial {
+        use super::search_by_gengou::case_array;
+        use super::*;
+
+        #[test]
+        fn return_spans_of_same_initial_file() {
+            let array = case_array();
+            let date = NaiveDate::from_ymd_opt(150, 1, 1).unwrap();
+
+            let result = search_by_initial(&array, &date, None, 'b');
+
+            assert_eq!(result.len(), 1);
+            assert_eq!(
+                result[0].span().end(),
+                &NaiveDate::from_ymd_opt(300, 1, 1).unwrap()
+            );
+        }
+
+        #[test]
+        fn return_different_emperors_of_bundled_courts() {
+            let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join(crate::span::SPAN_DIR_PATH)
+                .join(crate::span::TENNOU_DIR_PATH);
+            let array = crate::span::load_spans(&path).unwrap();
+            let date = NaiveDate::from_ymd_opt(1350, 1, 1).unwrap();
+
+            let names = |initial: char| -> Vec<String> {
+                search_by_initial(&array, &date, None, initial)
+                    .iter()
+                    .map(|v| v.gengou().to_string())
+                    .collect()
+            };
+            // 北朝・南朝で別の天皇になる
+            assert_eq!(names('h'), vec!["崇光天皇".to_string()]);
+            assert_eq!(names('n'), vec!["後村上天皇".to_string()]);
+        }
+
+        #[test]
+        fn return_spans_of_all_files_when_no_same_initial_file() {
+            let array = case_array();
+            let date = NaiveDate::from_ymd_opt(150, 1, 1).unwrap();
+
+            let result = search_by_initial(&array, &date, None, 'z');
+
+            assert_eq!(result.len(), 2);
+        }
+    }
+
+    mod is_covered {
+        use super::search_by_gengou::case_array;
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            let array = case_array();
+
+            let date = NaiveDate::from_ymd_opt(150, 1, 1).unwrap();
+            assert!(is_covered(&array, &date));
+            let date = NaiveDate::from_ymd_opt(1000, 1, 1).unwrap();
+            assert!(is_covered(&array, &date));
+            let date = NaiveDate::from_ymd_opt(1000, 1, 2).unwrap();
+            assert!(!is_covered(&array, &date));
+            let date = NaiveDate::from_ymd_opt(99, 12, 31).unwrap();
+            assert!(!is_covered(&array, &date));
+        }
+
+        #[test]
+        fn return_false_when_out_of_bundled_tennou_lists() {
+            let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join(crate::span::SPAN_DIR_PATH)
+                .join(crate::span::TENNOU_DIR_PATH);
+            let array = crate::span::load_spans(&path).unwrap();
+
+            // 同梱の一覧は南北朝時代と明治以降のみ
+            let date = NaiveDate::from_ymd_opt(1850, 6, 1).unwrap();
+            assert!(!is_covered(&array, &date));
+            let date = NaiveDate::from_ymd_opt(1350, 1, 1).unwrap();
+            assert!(is_covered(&array, &date));
+            let date = NaiveDate::from_ymd_opt(1868, 1, 1).unwrap();
+            assert!(is_covered(&array, &date));
+        }
+    }
+
+    mod search_range_from_array {
+        use super::search_by_gengou::case_array;
+        use super::*;
+
+        fn range(start: i32, end: i32) -> NaiveDateSpan {
+            NaiveDateSpan::new(
+                NaiveDate::from_ymd_opt(start, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(end, 1, 1).unwrap(),
+            )
+        }
+
+        fn gengou_list(result: &SearchedSpanList<'_>) -> Vec<(char, String)> {
+            result
+                .iter()
+                .flat_map(|(_, c, spans)| spans.iter().map(|v| (*c, v.gengou().to_string())))
+                .collect()
+        }
+
+        #[test]
+        fn return_all_overlapping_spans_in_order() {
+            let array = case_array();
+
+            let result = search_range_from_array(&array, &range(150, 350), &[]);
+            assert_eq!(
+                gengou_list(&result),
+                vec![
+                    ('a', "a".to_string()),
+                    ('a', "b".to_string()),
+                    ('b', "a".to_string()),
+                    ('b', "".to_string()),
+                ]
+            );
+
+            // 境界の日が同じなら重なるものとする
+            let result = search_range_from_array(&array, &range(50, 100), &[]);
+            assert_eq!(
+                gengou_list(&result),
+                vec![('a', "a".to_string()), ('b', "a".to_string())]
+            );
+        }
+
+        #[test]
+        fn return_nothing_when_out_of_range() {
+            let array = case_array();
+
+            assert!(search_range_from_array(&array, &range(1, 50), &[]).is_empty());
+            assert!(search_range_from_array(&array, &range(1001, 1100), &[]).is_empty());
+            assert!(search_range_from_array(&array, &range(150, 350), &["z"]).is_empty());
+        }
+    }
+
+    mod search_all_from_array {
+        use super::search_by_gengou::case_array;
+        use super::*;
+
+        #[test]
+        fn return_all_files_in_order() {
+            let array = case_array();
+
+            let result = search_all_from_array(&array, &[], None);
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0].1, 'a');
+            assert_eq!(result[0].2.len(), 2);
+            assert_eq!(result[1].1, 'b');
+            assert_eq!(result[1].2.len(), 3);
+        }
+
+        #[test]
+        fn return_filtered_files_when_selector_or_index_specified() {
+            let array = case_array();
+
+            let result = search_all_from_array(&array, &["b"], None);
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].1, 'b');
+
+            assert_eq!(search_all_from_array(&array, &[], Some(0)).len(), 2);
+            assert!(search_all_from_array(&array, &[], Some(1)).is_empty());
+        }
+    }
+
+    mod search_by_name {
+        use std::collections::HashMap;
+
+        use crate::span::{self, SpanList};
+
+        use super::*;
+
+        fn case_array() -> SpanListArray {
+            let contents = "\
+慶応,1865-05-01,1868-10-23,けいおう,Keio,慶應
+明治,1868-10-23,1912-07-30,めいじ,Meiji
+,1912-07-30,1912-07-31
+大正,1912-07-31,1926-12-25,たいしょう,Taisho
+昭和,1926-12-25,1989-01-07,しょうわ,Showa
+";
+            let (date_span, spans) = span::parse_contents(contents).unwrap().unwrap();
+            let mut map = HashMap::new();
+            map.insert('a', SpanList::new(date_span.clone(), 'a', spans));
+            vec![(date_span, map)]
+        }
+
+        fn gengou_list(result: &SearchedSpanList<'_>) -> Vec<String> {
+            result
+                .iter()
+                .flat_map(|(_, _, spans)| spans.iter().map(|v| v.gengou().to_string()))
+                .collect()
+        }
+
+        #[test]
+        fn return_spans_by_each_match() {
+            let array = case_array();
+            let cases = [
+                ("明治", NameMatch::Exact, vec!["明治"]),
+                ("慶應", NameMatch::Exact, vec!["慶応"]),
+                ("たいしょう", NameMatch::Reading, vec!["大正"]),
+                ("タイショウ", NameMatch::Reading, vec!["大正"]),
+                ("showa", NameMatch::Reading, vec!["昭和"]),
+                ("昭", NameMatch::Prefix, vec!["昭和"]),
+                ("しょ", NameMatch::Prefix, vec!["昭和"]),
+                ("明司", NameMatch::Similar, vec!["明治"]),
+                ("たいしよう", NameMatch::Similar, vec!["大正"]),
+            ];
+            for (name, expected_match, expected) in cases.iter() {
+                let (m, result) = search_by_name(&array, name, &[]);
+                assert_eq!(m, Some(*expected_match), "{}", name);
+                assert_eq!(gengou_list(&result), *expected, "{}", name);
+            }
+        }
+
+        #[test]
+        fn return_nothing_when_no_name_is_close() {
+            let array = case_array();
+
+            for name in ["", "平成", "x", "へいせい"] {
+                let (m, result) = search_by_name(&array, name, &[]);
+                assert_eq!(m, None, "{}", name);
+                assert!(result.is_empty(), "{}", name);
+            }
+            assert!(search_by_name(&array, "明治", &["z"]).1.is_empty());
+        }
+
+        #[test]
+        fn return_similar_spans_by_distance_and_start() {
+            let files = [
+                "かきくけさ,2000-01-01,2001-01-01\n\
+                 かきくしす,2001-01-01,2002-01-01\n\
+                 かきくけそ,2002-01-01,2003-01-01\n",
+                "かきくけち,1990-01-01,1991-01-01\n",
+            ];
+            let array: SpanListArray = files
+                .iter()
+                .map(|contents| {
+                    let (date_span, spans) = span::parse_contents(contents).unwrap().unwrap();
+                    let mut map = HashMap::new();
+                    map.insert('a', SpanList::new(date_span.clone(), 'a', spans));
+                    (date_span, map)
+                })
+                .collect();
+
+            let (m, result) = search_by_name(&array, "かきくけこ", &[]);
+            assert_eq!(m, Some(NameMatch::Similar));
+            assert_eq!(
+                gengou_list(&result),
+                vec!["かきくけち", "かきくけさ", "かきくけそ", "かきくしす"]
+            );
+        }
+    }
+
+    mod search_neighbor_from_array {
+        use std::collections::HashMap;
+
+        use crate::span::{self, SpanList};
+
+        use super::*;
+
+        fn case_array() -> SpanListArray {
+            let files = [
+                vec![('a', "x,2000-01-01,2001-01-01\ny,2001-01-01,2002-01-01\n")],
+                vec![
+                    (
+                        'h',
+                        "p,2002-01-01,2003-01-01\n,2003-01-01,2003-06-01\nq,2003-06-01,2004-01-01\n",
+                    ),
+                    ('n', "r,2002-01-01,2003-01-01\ns,2003-01-01,2004-01-01\n"),
+                ],
+                vec![('a', "t,2004-01-01,2005-01-01\n")],
+            ];
+            files
+                .iter()
+                .map(|lists| {
+                    let mut map = HashMap::new();
+                    let mut date_span = None;
+                    for (initial, contents) in lists.iter() {
+                        let (s, spans) = span::parse_contents(contents).unwrap().unwrap();
+                        map.insert(*initial, SpanList::new(s.clone(), *initial, spans));
+                        date_span = Some(s);
+                    }
+                    (date_span.unwrap(), map)
+                })
+                .collect()
+        }
+
+        fn gengou_list(result: &SearchedSpanList<'_>) -> Vec<(char, String)> {
+            result
+                .iter()
+                .flat_map(|(_, c, spans)| spans.iter().map(|v| (*c, v.gengou().to_string())))
+                .collect()
+        }
+
+        fn neighbors(gengou: &str, direction: Direction, selector: &[&str]) -> Vec<(char, String)> {
+            let array = case_array();
+            gengou_list(&search_neighbor_from_array(
+                &array, gengou, direction, selector,
+            ))
+        }
+
+        #[test]
+        fn return_neighbor_in_same_file() {
+            assert_eq!(
+                neighbors("x", Direction::Next, &[]),
+                vec![('a', "y".to_string())]
+            );
+            // 元号のない期間は飛ばす
+            assert_eq!(
+                neighbors("p", Direction::Next, &[]),
+                vec![('h', "q".to_string())]
+            );
+            assert_eq!(
+                neighbors("q", Direction::Previous, &[]),
+                vec![('h', "p".to_string())]
+            );
+        }
+
+        #[test]
+        fn return_neighbor_across_files() {
+            // 同じ頭文字のファイルがなければ、その範囲の全てのファイルから
+            assert_eq!(
+                neighbors("y", Direction::Next, &[]),
+                vec![('h', "p".to_string()), ('n', "r".to_string())]
+            );
+            assert_eq!(
+                neighbors("t", Direction::Previous, &[]),
+                vec![('h', "q".to_string()), ('n', "s".to_string())]
+            );
+            assert_eq!(
+                neighbors("p", Direction::Previous, &[]),
+                vec![('a', "y".to_string())]
+            );
+            assert_eq!(
+                neighbors("s", Direction::Next, &[]),
+                vec![('a', "t".to_string())]
+            );
+        }
+
+        #[test]
+        fn return_neighbor_of_selected_files() {
+            assert_eq!(
+                neighbors("y", Direction::Next, &["", "h"]),
+                vec![('h', "p".to_string())]
+            );
+            assert!(neighbors("p", Direction::Next, &["", "n"]).is_empty());
+        }
+
+        #[test]
+        fn return_nothing_when_no_neighbor() {
+            assert!(neighbors("x", Direction::Previous, &[]).is_empty());
+            assert!(neighbors("t", Direction::Next, &[]).is_empty());
+            assert!(neighbors("z", Direction::Next, &[]).is_empty());
+            assert!(neighbors("", Direction::Next, &[]).is_empty());
+        }
+    }
+
     mod search_from_list {
         use super::*;
 
@@ -1,13 +1,12 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::Read,
-    path::{Path, PathBuf},
-};
+use std::{collections::HashMap, fs::File, io::Read, path::Path};
 
-use chrono::NaiveDate;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime};
 
-const SPAN_DIR_PATH: &str = "gengou_lists";
+pub(crate) const SPAN_DIR_PATH: &str = "gengou_lists";
+/// 天皇の一覧のディレクトリ(`gengou_lists`内)
+pub(crate) const TENNOU_DIR_PATH: &str = "tennou";
+/// 時差が指定されていない時刻の時差(日本標準時、UTC+9)
+const DEFAULT_OFFSET_SECS: i32 = 9 * 3600;
 
 pub(crate) type SpanListArray = Vec<(NaiveDateSpan, HashMap<char, SpanList>)>;
 
@@ -22,6 +21,28 @@ pub(crate) struct SpanList {
 pub(crate) struct Span {
     gengou: String,
     span: NaiveDateSpan,
+    reading: Option<String>,
+    romaji: Option<String>,
+    variants: Vec<String>,
+    attributes: Vec<(String, String)>,
+    /// 始期の時刻。指定されていなければ始期の日の0時から
+    start_time: Option<DateTime<FixedOffset>>,
+    /// 終期の時刻。指定されていなければ終期の翌日の0時まで
+    end_time: Option<DateTime<FixedOffset>>,
+    /// 終期が`****`(今日まで続いている)か
+    ongoing: bool,
+}
+
+/// 旧暦で表すときの元号の年の数え方(属性`rule`)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum CountingRule {
+    /// 始期の年を元年とする
+    #[default]
+    Calendar,
+    /// 立年改元。始期の年を元年とする(その年の初めに遡る)
+    Ritsunen,
+    /// 踰年改元。旧暦で表すときは、始期の翌年を元年とする
+    Yunen,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -50,6 +71,39 @@ impl SpanList {
     pub fn spans(&self) -> &Vec<Span> {
         &self.spans
     }
+
+    /// `span`の前の元号。元号のない期間は飛ばす。
+    /// 最初の元号のとき、または`span`がこの一覧にないときはNoneを返す
+    pub fn previous(&self, span: &Span) -> Option<&Span> {
+        let position = self.position(span)?;
+        self.spans[..position]
+            .iter()
+            .rev()
+            .find(|v| !v.gengou.is_empty())
+    }
+
+    /// `span`の次の元号。元号のない期間は飛ばす。
+    /// 最後の元号のとき、または`span`がこの一覧にないときはNoneを返す
+    pub fn next(&self, span: &Span) -> Option<&Span> {
+        let position = self.position(span)?;
+        self.spans[position + 1..]
+            .iter()
+            .find(|v| !v.gengou.is_empty())
+    }
+
+    /// 最初の元号(元号のない期間を除く)
+    pub fn first_named(&self) -> Option<&Span> {
+        self.spans.iter().find(|v| !v.gengou.is_empty())
+    }
+
+    /// 最後の元号(元号のない期間を除く)
+    pub fn last_named(&self) -> Option<&Span> {
+        self.spans.iter().rev().find(|v| !v.gengou.is_empty())
+    }
+
+    fn position(&self, span: &Span) -> Option<usize> {
+        self.spans.iter().position(|v| std::ptr::eq(v, span))
+    }
 }
 
 impl Span {
@@ -57,9 +111,52 @@ impl Span {
         Span {
             gengou,
             span: NaiveDateSpan::new(start, end),
+            reading: None,
+            romaji: None,
+            variants: Vec::new(),
+            attributes: Vec::new(),
+            start_time: None,
+            end_time: None,
+            ongoing: false,
         }
     }
 
+    /// 読み(ひらがな)、ローマ字表記、異表記(旧字体など)を設定する
+    pub fn with_names(
+        mut self,
+        reading: Option<String>,
+        romaji: Option<String>,
+        variants: Vec<String>,
+    ) -> Span {
+        self.reading = reading;
+        self.romaji = romaji;
+        self.variants = variants;
+        self
+    }
+
+    /// `key=value`の形式の属性を設定する
+    pub fn with_attributes(mut self, attributes: Vec<(String, String)>) -> Span {
+        self.attributes = attributes;
+        self
+    }
+
+    /// 始期・終期の時刻を設定する
+    pub fn with_times(
+        mut self,
+        start_time: Option<DateTime<FixedOffset>>,
+        end_time: Option<DateTime<FixedOffset>>,
+    ) -> Span {
+        self.start_time = start_time;
+        self.end_time = end_time;
+        self
+    }
+
+    /// 終期が`****`(今日まで続いている)かを設定する
+    pub fn with_ongoing(mut self, ongoing: bool) -> Span {
+        self.ongoing = ongoing;
+        self
+    }
+
     pub fn gengou(&self) -> &str {
         &self.gengou
     }
@@ -67,6 +164,58 @@ impl Span {
     pub fn span(&self) -> &NaiveDateSpan {
         &self.span
     }
+
+    pub fn is_ongoing(&self) -> bool {
+        self.ongoing
+    }
+
+    pub fn reading(&self) -> Option<&str> {
+        self.reading.as_deref()
+    }
+
+    pub fn romaji(&self) -> Option<&str> {
+        self.romaji.as_deref()
+    }
+
+    pub fn variants(&self) -> &Vec<String> {
+        &self.variants
+    }
+
+    /// `key`の属性の値。なければNoneを返す
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 元号の年の数え方。属性`rule`がない(または不明な)ときは出力する暦の年で数える
+    pub fn counting_rule(&self) -> CountingRule {
+        self.attribute("rule")
+            .and_then(parse_counting_rule)
+            .unwrap_or_default()
+    }
+
+    /// `instant`がこの期間に含まれるか。
+    /// 時刻の指定されていない始期・終期は、日本標準時の日の境目とする
+    pub fn contains_instant(&self, instant: &DateTime<FixedOffset>) -> bool {
+        let midnight = |date: NaiveDate| {
+            date.and_time(NaiveTime::MIN)
+                .and_local_timezone(default_offset())
+                .single()
+        };
+        let start = self.start_time.or_else(|| midnight(self.span.start));
+        let end = self
+            .end_time
+            .or_else(|| self.span.end.succ_opt().and_then(midnight));
+
+        start.is_none_or(|v| v <= *instant) && end.is_none_or(|v| *instant < v)
+    }
+
+    /// 元号または異表記のいずれかが`name`と一致するか
+    pub fn is_named(&self, name: &str) -> bool {
+        self.gengou == name || self.variants.iter().any(|v| v == name)
+    }
 }
 
 impl NaiveDateSpan {
@@ -81,6 +230,13 @@ impl NaiveDateSpan {
     pub fn end(&self) -> &NaiveDate {
         &self.end
     }
+
+    /// `other`と重なる期間。重ならなければNoneを返す
+    pub fn intersection(&self, other: &NaiveDateSpan) -> Option<NaiveDateSpan> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        (start <= end).then(|| NaiveDateSpan::new(start, end))
+    }
 }
 
 impl PartialOrd for NaiveDateSpan {
@@ -98,13 +254,19 @@ impl Ord for NaiveDateSpan {
     }
 }
 
-pub(crate) fn load_spans(base_path: &Path) -> Result<SpanListArray, std::io::Error> {
-    let path = PathBuf::from(base_path).join(SPAN_DIR_PATH);
+/// `path`で指定されたディレクトリ(`gengou_lists`)内のファイルを読み込む
+pub(crate) fn load_spans(path: &Path) -> Result<SpanListArray, std::io::Error> {
     let mut span_list_array: SpanListArray = Vec::new();
 
+    // 頭文字が重複したときに結果が変わらないよう、ファイル名の順に読み込む
+    let mut entries = Vec::new();
     for entry in path.read_dir()? {
-        let dir = entry?;
-        if let Some(span_list) = parse_span_list_file(&dir.path())? {
+        entries.push(entry?.path());
+    }
+    entries.sort();
+
+    for entry in entries {
+        if let Some(span_list) = parse_span_list_file(&entry)? {
             let naive_date_span = span_list.span().clone();
             let initial = *span_list.initial();
 
@@ -146,7 +308,11 @@ fn parse_span_list_file(path: &Path) -> Result<Option<SpanList>, std::io::Error>
     let mut contents = String::new();
     fs.read_to_string(&mut contents)?;
 
-    let (span, spans) = if let Some(v) = parse_contents(&contents)? {
+    let parsed = parse_contents(&contents).map_err(|e| {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        with_location(e, file_name.to_string())
+    })?;
+    let (span, spans) = if let Some(v) = parsed {
         v
     } else {
         return Ok(None);
@@ -155,7 +321,7 @@ fn parse_span_list_file(path: &Path) -> Result<Option<SpanList>, std::io::Error>
     Ok(Some(SpanList::new(span, initial, spans)))
 }
 
-fn is_target_filestem(s: &str) -> bool {
+pub(crate) fn is_target_filestem(s: &str) -> bool {
     // '00'で始まること
     if !s.starts_with("00") {
         return false;
@@ -174,16 +340,20 @@ fn is_target_filestem(s: &str) -> bool {
     true
 }
 
-fn get_initial_char_after_underbar(s: &str) -> Option<char> {
+pub(crate) fn get_initial_char_after_underbar(s: &str) -> Option<char> {
     s.split_once('_').and_then(|(_lhs, rhs)| rhs.chars().next())
 }
 
-fn parse_contents(contents: &str) -> Result<Option<(NaiveDateSpan, Vec<Span>)>, std::io::Error> {
+pub(crate) fn parse_contents(
+    contents: &str,
+) -> Result<Option<(NaiveDateSpan, Vec<Span>)>, std::io::Error> {
     let lines = contents.lines();
     let mut spans = Vec::new();
     let method = |target: &Span, value: &Span| target.span() < value.span();
-    for line in lines {
-        if let Some(line_span) = parse_line(line)? {
+    for (index, line) in lines.enumerate() {
+        let line_span =
+            parse_line(line).map_err(|e| with_location(e, format!("line {}", index + 1)))?;
+        if let Some(line_span) = line_span {
             insertion_sort(&mut spans, line_span, method);
         }
     }
@@ -199,33 +369,71 @@ fn parse_contents(contents: &str) -> Result<Option<(NaiveDateSpan, Vec<Span>)>,
 }
 
 fn parse_line(s: &str) -> Result<Option<Span>, std::io::Error> {
-    // コメント処理
-    let comment_point = s.find("//");
-    let body = if let Some(point) = comment_point {
-        let (lhs, _rhs) = s.split_at(point);
-        lhs.trim()
-    } else {
-        s.trim()
-    };
     // 空行ならNoneを返す
-    if body.is_empty() {
+    let fields = if let Some(f) = split_line(s) {
+        f
+    } else {
         return Ok(None);
-    }
-
-    let mut splited = body.split(',');
+    };
 
-    if let (Some(gengou), Some(start_str), Some(end_str)) =
-        (splited.next(), splited.next(), splited.next())
-    {
-        let start = parse_datetime(start_str.trim())?;
-        let end = parse_datetime(end_str.trim())?;
+    if let [(_, gengou), (start_column, start_str), (end_column, end_str), ..] = fields[..] {
+        let start = parse_boundary(start_str)
+            .map_err(|e| with_location(e, format!("column {}", start_column)))?;
+        let end = parse_boundary(end_str)
+            .map_err(|e| with_location(e, format!("column {}", end_column)))?;
 
         // 逆になっていたら直す
-        if end < start {
-            Ok(Some(Span::new(gengou.trim().to_string(), end, start)))
+        let is_reversed = end.0 < start.0;
+        let ((start, start_time), (end, end_time)) = if is_reversed {
+            (end, start)
         } else {
-            Ok(Some(Span::new(gengou.trim().to_string(), start, end)))
+            (start, end)
+        };
+        let ongoing = if is_reversed { start_str } else { end_str } == "****";
+        // 時刻付きの終期の日は、日付のみで探すときは次の元号の日とする
+        let end = if end_time.is_some() {
+            end.pred_opt().unwrap_or(end).max(start)
+        } else {
+            end
+        };
+        let span = Span::new(gengou.to_string(), start, end)
+            .with_times(start_time, end_time)
+            .with_ongoing(ongoing);
+
+        // 4列目以降(読み、ローマ字表記、`|`区切りの異表記)は省略できる
+        let optional = |index: usize| {
+            fields
+                .get(index)
+                .map(|(_, v)| v.to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let variants = optional(5).map_or(Vec::new(), |v| {
+            v.split('|')
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .collect()
+        });
+
+        // 7列目以降は`key=value`の形式の属性
+        let mut attributes = Vec::new();
+        for (column, field) in fields.iter().skip(6).filter(|(_, v)| !v.is_empty()) {
+            if let Some((key, value)) = parse_attribute(field) {
+                attributes.push((key.to_string(), value.to_string()));
+            } else {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!(
+                        "column {}: attribute format is invalid. the format is 'key=value'.",
+                        column
+                    ),
+                ));
+            }
         }
+
+        Ok(Some(
+            span.with_names(optional(3), optional(4), variants)
+                .with_attributes(attributes),
+        ))
     } else {
         Err(std::io::Error::new(
             std::io::ErrorKind::InvalidData,
@@ -234,7 +442,104 @@ fn parse_line(s: &str) -> Result<Option<Span>, std::io::Error> {
     }
 }
 
-fn parse_datetime(s: &str) -> Result<NaiveDate, std::io::Error> {
+/// 属性`rule`の値(`ritsunen`・`立年改元`、`yunen`・`踰年改元`)
+pub(crate) fn parse_counting_rule(s: &str) -> Option<CountingRule> {
+    match s {
+        "ritsunen" | "立年改元" => Some(CountingRule::Ritsunen),
+        "yunen" | "踰年改元" => Some(CountingRule::Yunen),
+        _ => None,
+    }
+}
+
+/// `key=value`の形式の属性を分ける
+pub(crate) fn parse_attribute(s: &str) -> Option<(&str, &str)> {
+    s.split_once('=')
+        .map(|(k, v)| (k.trim(), v.trim()))
+        .filter(|(k, _)| !k.is_empty())
+}
+
+/// コメントを除いた行を`,`で区切り、各要素とその列(1始まり、文字単位)を返す。
+/// 空行ならNoneを返す。
+pub(crate) fn split_line(s: &str) -> Option<Vec<(usize, &str)>> {
+    // コメント処理
+    let body = s.find("//").map_or(s, |point| &s[..point]);
+    if body.trim().is_empty() {
+        return None;
+    }
+
+    let mut fields = Vec::new();
+    let mut offset = 0;
+    for part in body.split(',') {
+        let leading = part.len() - part.trim_start().len();
+        let column = s[..offset + leading].chars().count() + 1;
+        fields.push((column, part.trim()));
+        offset += part.len() + 1;
+    }
+
+    Some(fields)
+}
+
+/// エラーの内容の前に、発生した場所を付け加える
+fn with_location(e: std::io::Error, location: String) -> std::io::Error {
+    std::io::Error::new(e.kind(), format!("{}: {}", location, e))
+}
+
+/// 始期・終期の日付と、指定されていれば時刻(`1926-12-25T01:25`、`1926-12-25T01:25+09:00`)。
+/// 時刻があれば、日付はその時点の日本標準時の日付とする
+pub(crate) fn parse_boundary(
+    s: &str,
+) -> Result<(NaiveDate, Option<DateTime<FixedOffset>>), std::io::Error> {
+    let (date_str, time_str) = match s.split_once('T') {
+        Some((d, t)) => (d, Some(t)),
+        None => (s, None),
+    };
+    let date = parse_datetime(date_str)?;
+
+    let time = match time_str {
+        Some(t) => Some(
+            parse_time(t)
+                .and_then(|(time, offset)| date.and_time(time).and_local_timezone(offset).single())
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "time format is invalid. the format is '%Y-%m-%dT%H:%M[:%S][+09:00]'",
+                    )
+                })?,
+        ),
+        None => None,
+    };
+    let date = time.map_or(date, |v| v.with_timezone(&default_offset()).date_naive());
+
+    Ok((date, time))
+}
+
+/// 時刻と時差(`01:25`、`01:25:30`、`16:25Z`、`01:25+09:00`)。
+/// 時差がなければ日本標準時とする
+pub(crate) fn parse_time(s: &str) -> Option<(NaiveTime, FixedOffset)> {
+    let (time_str, offset) = if let Some(t) = s.strip_suffix('Z') {
+        (t, FixedOffset::east_opt(0)?)
+    } else if let Some(position) = s.rfind(['+', '-']) {
+        let (t, o) = s.split_at(position);
+        let sign = if o.starts_with('-') { -1 } else { 1 };
+        let (hours, minutes) = o[1..].split_once(':')?;
+        let seconds = hours.parse::<i32>().ok()? * 3600 + minutes.parse::<i32>().ok()? * 60;
+        (t, FixedOffset::east_opt(sign * seconds)?)
+    } else {
+        (s, default_offset())
+    };
+
+    let time = NaiveTime::parse_from_str(time_str, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time_str, "%H:%M"))
+        .ok()?;
+
+    Some((time, offset))
+}
+
+pub(crate) fn default_offset() -> FixedOffset {
+    FixedOffset::east_opt(DEFAULT_OFFSET_SECS).expect("valid offset")
+}
+
+pub(crate) fn parse_datetime(s: &str) -> Result<NaiveDate, std::io::Error> {
     NaiveDate::parse_from_str(s, "%Y-%m-%d").or_else(|_| {
         if s.eq("****") {
             Ok(chrono::Local::now().date_naive())
@@ -264,17 +569,54 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
     use super::*;
 
+    mod span_list {
+        use super::*;
+
+        fn case_list() -> SpanList {
+            let contents =
+                "a,2000-01-01,2000-02-01\n,2000-02-01,2000-03-01\nb,2000-03-01,2000-04-01\n";
+            let (span, spans) = parse_contents(contents).unwrap().unwrap();
+            SpanList::new(span, 'a', spans)
+        }
+
+        #[test]
+        fn return_neighbor_skipping_empty_gengou() {
+            let list = case_list();
+            let spans = list.spans();
+
+            assert_eq!(list.next(&spans[0]).map(|v| v.gengou()), Some("b"));
+            assert_eq!(list.previous(&spans[2]).map(|v| v.gengou()), Some("a"));
+            assert_eq!(list.next(&spans[1]).map(|v| v.gengou()), Some("b"));
+            assert_eq!(list.first_named().map(|v| v.gengou()), Some("a"));
+            assert_eq!(list.last_named().map(|v| v.gengou()), Some("b"));
+        }
+
+        #[test]
+        fn return_none_when_no_neighbor() {
+            let list = case_list();
+            let spans = list.spans();
+
+            assert_eq!(list.previous(&spans[0]), None);
+            assert_eq!(list.next(&spans[2]), None);
+            // 一覧にない元号
+            let other = spans[0].clone();
+            assert_eq!(list.next(&other), None);
+        }
+    }
+
     mod load_spans {
         use super::*;
 
         #[test]
         fn success_when_valid_path_to_dir() {
-            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(SPAN_DIR_PATH);
             let result = load_spans(&path).unwrap();
 
-            let expect = vec![
+            let expect = [
                 (
                     1,
                     NaiveDateSpan::new(
@@ -285,7 +627,7 @@ mod tests {
                 (
                     2,
                     NaiveDateSpan::new(
-                        NaiveDate::from_ymd_opt(1329, 09, 30).unwrap(),
+                        NaiveDate::from_ymd_opt(1329, 9, 30).unwrap(),
                         NaiveDate::from_ymd_opt(1394, 8, 10).unwrap(),
                     ),
                 ),
@@ -328,73 +670,73 @@ mod tests {
             assert_eq!(result.initial(), &'n');
             assert_eq!(
                 result.span().start(),
-                &NaiveDate::from_ymd_opt(1329, 09, 30).unwrap()
+                &NaiveDate::from_ymd_opt(1329, 9, 30).unwrap()
             );
             assert_eq!(
                 result.span().end(),
-                &NaiveDate::from_ymd_opt(1394, 08, 10).unwrap(),
+                &NaiveDate::from_ymd_opt(1394, 8, 10).unwrap(),
             );
 
             let expect = vec![
                 Span::new(
                     "元徳".to_string(),
-                    NaiveDate::from_ymd_opt(1329, 09, 30).unwrap(),
-                    NaiveDate::from_ymd_opt(1331, 09, 18).unwrap(),
+                    NaiveDate::from_ymd_opt(1329, 9, 30).unwrap(),
+                    NaiveDate::from_ymd_opt(1331, 9, 18).unwrap(),
                 ),
                 Span::new(
                     "元弘".to_string(),
-                    NaiveDate::from_ymd_opt(1331, 09, 18).unwrap(),
-                    NaiveDate::from_ymd_opt(1334, 03, 13).unwrap(),
+                    NaiveDate::from_ymd_opt(1331, 9, 18).unwrap(),
+                    NaiveDate::from_ymd_opt(1334, 3, 13).unwrap(),
                 ),
                 Span::new(
                     "建武".to_string(),
-                    NaiveDate::from_ymd_opt(1334, 03, 13).unwrap(),
-                    NaiveDate::from_ymd_opt(1336, 04, 19).unwrap(),
+                    NaiveDate::from_ymd_opt(1334, 3, 13).unwrap(),
+                    NaiveDate::from_ymd_opt(1336, 4, 19).unwrap(),
                 ),
                 Span::new(
                     "延元".to_string(),
-                    NaiveDate::from_ymd_opt(1336, 04, 19).unwrap(),
-                    NaiveDate::from_ymd_opt(1340, 06, 02).unwrap(),
+                    NaiveDate::from_ymd_opt(1336, 4, 19).unwrap(),
+                    NaiveDate::from_ymd_opt(1340, 6, 2).unwrap(),
                 ),
                 Span::new(
                     "興国".to_string(),
-                    NaiveDate::from_ymd_opt(1340, 06, 02).unwrap(),
-                    NaiveDate::from_ymd_opt(1347, 01, 28).unwrap(),
+                    NaiveDate::from_ymd_opt(1340, 6, 2).unwrap(),
+                    NaiveDate::from_ymd_opt(1347, 1, 28).unwrap(),
                 ),
                 Span::new(
                     "正平".to_string(),
-                    NaiveDate::from_ymd_opt(1347, 01, 28).unwrap(),
-                    NaiveDate::from_ymd_opt(1370, 08, 24).unwrap(),
+                    NaiveDate::from_ymd_opt(1347, 1, 28).unwrap(),
+                    NaiveDate::from_ymd_opt(1370, 8, 24).unwrap(),
                 ),
                 Span::new(
                     "建徳".to_string(),
-                    NaiveDate::from_ymd_opt(1370, 08, 24).unwrap(),
-                    NaiveDate::from_ymd_opt(1372, 05, 09).unwrap(),
+                    NaiveDate::from_ymd_opt(1370, 8, 24).unwrap(),
+                    NaiveDate::from_ymd_opt(1372, 5, 9).unwrap(),
                 ),
                 Span::new(
                     "文中".to_string(),
-                    NaiveDate::from_ymd_opt(1372, 05, 09).unwrap(),
-                    NaiveDate::from_ymd_opt(1375, 07, 04).unwrap(),
+                    NaiveDate::from_ymd_opt(1372, 5, 9).unwrap(),
+                    NaiveDate::from_ymd_opt(1375, 7, 4).unwrap(),
                 ),
                 Span::new(
                     "天授".to_string(),
-                    NaiveDate::from_ymd_opt(1375, 07, 04).unwrap(),
-                    NaiveDate::from_ymd_opt(1381, 03, 14).unwrap(),
+                    NaiveDate::from_ymd_opt(1375, 7, 4).unwrap(),
+                    NaiveDate::from_ymd_opt(1381, 3, 14).unwrap(),
                 ),
                 Span::new(
                     "弘和".to_string(),
-                    NaiveDate::from_ymd_opt(1381, 03, 14).unwrap(),
-                    NaiveDate::from_ymd_opt(1384, 05, 26).unwrap(),
+                    NaiveDate::from_ymd_opt(1381, 3, 14).unwrap(),
+                    NaiveDate::from_ymd_opt(1384, 5, 26).unwrap(),
                 ),
                 Span::new(
                     "元中".to_string(),
-                    NaiveDate::from_ymd_opt(1384, 05, 26).unwrap(),
+                    NaiveDate::from_ymd_opt(1384, 5, 26).unwrap(),
                     NaiveDate::from_ymd_opt(1392, 11, 27).unwrap(),
                 ),
                 Span::new(
                     "明徳".to_string(),
                     NaiveDate::from_ymd_opt(1392, 11, 27).unwrap(),
-                    NaiveDate::from_ymd_opt(1394, 08, 10).unwrap(),
+                    NaiveDate::from_ymd_opt(1394, 8, 10).unwrap(),
                 ),
             ];
 
@@ -415,11 +757,11 @@ mod tests {
             assert_eq!(result.initial(), &'h');
             assert_eq!(
                 result.span().start(),
-                &NaiveDate::from_ymd_opt(1329, 09, 30).unwrap()
+                &NaiveDate::from_ymd_opt(1329, 9, 30).unwrap()
             );
             assert_eq!(
                 result.span().end(),
-                &NaiveDate::from_ymd_opt(1394, 08, 10).unwrap(),
+                &NaiveDate::from_ymd_opt(1394, 8, 10).unwrap(),
             );
         }
 
@@ -490,13 +832,13 @@ mod tests {
     平成,1989-01-08,2019-04-30
     令和,2019-05-01,****
     "#;
-            let (span, spans) = parse_contents(&case).unwrap().unwrap();
+            let (span, spans) = parse_contents(case).unwrap().unwrap();
             let now = chrono::Local::now().date_naive();
 
             assert_eq!(span.start(), &NaiveDate::from_ymd_opt(1868, 1, 25).unwrap());
             assert_eq!(span.end(), &now);
 
-            let expect = vec![
+            let expect = [
                 Span::new(
                     "明治".to_string(),
                     NaiveDate::from_ymd_opt(1868, 1, 25).unwrap(),
@@ -520,7 +862,7 @@ mod tests {
                 Span::new(
                     "令和".to_string(),
                     NaiveDate::from_ymd_opt(2019, 5, 1).unwrap(),
-                    now.clone(),
+                    now,
                 ),
             ];
 
@@ -540,13 +882,13 @@ mod tests {
 昭和,1989-01-07,1926-12-25
 大正,1926-12-24,1912-07-30
     "#;
-            let (span, spans) = parse_contents(&case).unwrap().unwrap();
+            let (span, spans) = parse_contents(case).unwrap().unwrap();
             let now = chrono::Local::now().date_naive();
 
             assert_eq!(span.start(), &NaiveDate::from_ymd_opt(1868, 1, 25).unwrap());
             assert_eq!(span.end(), &now);
 
-            let expect = vec![
+            let expect = [
                 Span::new(
                     "明治".to_string(),
                     NaiveDate::from_ymd_opt(1868, 1, 25).unwrap(),
@@ -570,7 +912,7 @@ mod tests {
                 Span::new(
                     "令和".to_string(),
                     NaiveDate::from_ymd_opt(2019, 5, 1).unwrap(),
-                    now.clone(),
+                    now,
                 ),
             ];
 
@@ -593,6 +935,13 @@ mod tests {
             assert!(parse_contents(case).is_err());
         }
 
+        #[test]
+        fn failed_with_location_when_containing_invalid_line() {
+            let case = "令和,2019-05-01,****\n平成,1989-01-08,2019_04-30\n";
+            let e = parse_contents(case).unwrap_err();
+            assert!(e.to_string().starts_with("line 2: column 15: "));
+        }
+
         #[test]
         fn success_and_return_none_when_empty_data() {
             let case = r#"
@@ -602,7 +951,7 @@ mod tests {
 //昭和,1926-12-25,1989-01-07
 //大正,1912-07-30,1926-12-24
     "#;
-            assert!(parse_contents(&case).unwrap().is_none());
+            assert!(parse_contents(case).unwrap().is_none());
         }
     }
 
@@ -650,12 +999,81 @@ mod tests {
             );
         }
 
+        #[test]
+        fn success_and_return_some_when_valid_line_with_times() {
+            let case = "大正,1912-07-30T00:43,1926-12-25T01:25";
+            let result = parse_line(case).unwrap().unwrap();
+            // 時刻付きの終期の日は、日付のみでは次の元号の日とする
+            assert_eq!(
+                result.span(),
+                &NaiveDateSpan::new(
+                    NaiveDate::from_ymd_opt(1912, 7, 30).unwrap(),
+                    NaiveDate::from_ymd_opt(1926, 12, 24).unwrap()
+                )
+            );
+
+            let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+            let instant = |d: u32, h: u32, m: u32| {
+                NaiveDate::from_ymd_opt(1926, 12, d)
+                    .unwrap()
+                    .and_hms_opt(h, m, 0)
+                    .unwrap()
+                    .and_local_timezone(jst)
+                    .unwrap()
+            };
+            assert!(result.contains_instant(&instant(25, 1, 24)));
+            assert!(!result.contains_instant(&instant(25, 1, 25)));
+
+            let case = "昭和,1926-12-25T01:25,1989-01-07";
+            let result = parse_line(case).unwrap().unwrap();
+            assert!(!result.contains_instant(&instant(25, 1, 24)));
+            assert!(result.contains_instant(&instant(25, 1, 25)));
+        }
+
+        #[test]
+        fn success_and_return_some_when_valid_line_with_names() {
+            let case = "慶応,1865-05-01,1868-10-23,けいおう,Keio,慶應|慶应 // comment";
+            let result = parse_line(case).unwrap().unwrap();
+            assert_eq!(result.gengou(), "慶応");
+            assert_eq!(result.reading(), Some("けいおう"));
+            assert_eq!(result.romaji(), Some("Keio"));
+            assert_eq!(
+                result.variants(),
+                &vec!["慶應".to_string(), "慶应".to_string()]
+            );
+            assert!(result.is_named("慶應"));
+
+            let case = "慶応,1865-05-01,1868-10-23,,Keio";
+            let result = parse_line(case).unwrap().unwrap();
+            assert_eq!(result.reading(), None);
+            assert_eq!(result.romaji(), Some("Keio"));
+            assert!(result.variants().is_empty());
+
+            let case = "明治天皇,1867-02-13,1912-07-29,,,,reign=122, note = a=b";
+            let result = parse_line(case).unwrap().unwrap();
+            assert_eq!(result.attribute("reign"), Some("122"));
+            assert_eq!(result.attribute("note"), Some("a=b"));
+            assert_eq!(result.attribute("rule"), None);
+
+            let case = "慶応,1865-05-01,1868-10-23";
+            let result = parse_line(case).unwrap().unwrap();
+            assert_eq!(result.reading(), None);
+            assert_eq!(result.romaji(), None);
+            assert!(!result.is_named("慶應"));
+        }
+
         #[test]
         fn failed_when_invalid_lines_parts() {
             let case = ",655-02-15";
             assert!(parse_line(case).is_err());
         }
 
+        #[test]
+        fn failed_when_invalid_attribute() {
+            let case = "明治天皇,1867-02-13,1912-07-29,,,,122";
+            assert!(parse_line(case).is_err());
+        }
+
         #[test]
         fn failed_when_invalid_dateformat() {
             let case = ",655-02-15,686_08-17";
@@ -663,6 +1081,65 @@ mod tests {
         }
     }
 
+    mod split_line {
+        use super::*;
+
+        #[test]
+        fn return_none_when_only_comment_or_empty() {
+            assert_eq!(split_line(" // comment"), None);
+            assert_eq!(split_line("   "), None);
+        }
+
+        #[test]
+        fn return_fields_with_column() {
+            let result = split_line("大化, 645-08-01 ,650-03-25 // comment").unwrap();
+            assert_eq!(
+                result,
+                vec![(1, "大化"), (5, "645-08-01"), (16, "650-03-25")]
+            );
+        }
+    }
+
+    mod parse_boundary {
+        use super::*;
+
+        #[test]
+        fn success_when_valid_format_str() {
+            let date = NaiveDate::from_ymd_opt(1926, 12, 25).unwrap();
+            assert_eq!(parse_boundary("1926-12-25").unwrap(), (date, None));
+
+            let expected = date
+                .and_hms_opt(1, 25, 0)
+                .unwrap()
+                .and_local_timezone(FixedOffset::east_opt(9 * 3600).unwrap())
+                .unwrap();
+            assert_eq!(
+                parse_boundary("1926-12-25T01:25").unwrap(),
+                (date, Some(expected))
+            );
+            assert_eq!(
+                parse_boundary("1926-12-25T01:25:00+09:00").unwrap(),
+                (date, Some(expected))
+            );
+            // 日本標準時では翌日
+            assert_eq!(
+                parse_boundary("1926-12-25T16:25Z").unwrap(),
+                (
+                    NaiveDate::from_ymd_opt(1926, 12, 26).unwrap(),
+                    Some(expected + chrono::TimeDelta::days(1))
+                )
+            );
+        }
+
+        #[test]
+        fn failed_when_invalid_time_str() {
+            assert!(parse_boundary("1926-12-25T").is_err());
+            assert!(parse_boundary("1926-12-25T1:2:3:4").is_err());
+            assert!(parse_boundary("1926-12-25T01:25+0900").is_err());
+            assert!(parse_boundary("1926-12-32T01:25").is_err());
+        }
+    }
+
     mod parse_datetime {
         use chrono::Datelike;
 
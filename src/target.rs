@@ -0,0 +1,431 @@
+use std::fmt;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone};
+
+use crate::julian::JulianDate;
+use crate::span;
+
+/// 一つの文字列で指定された日付の形式
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TargetForm {
+    /// `2023-10-01`
+    Iso,
+    /// `2023/10/01`
+    Slashed,
+    /// `20231001`
+    Compact,
+    /// `@`を付けたUnix時間(秒)。`@1696086000`
+    Timestamp,
+    /// `today`
+    Today,
+    /// `now`(時刻を含む)
+    Now,
+}
+
+impl fmt::Display for TargetForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            TargetForm::Iso => "ISO 8601 (%Y-%m-%d)",
+            TargetForm::Slashed => "slashed (%Y/%m/%d)",
+            TargetForm::Compact => "compact (%Y%m%d)",
+            TargetForm::Timestamp => "unix timestamp (@seconds)",
+            TargetForm::Today => "today",
+            TargetForm::Now => "now",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// 日付の指定に使用する引数の数。
+/// 年・月・日を別々に指定したとき(Argument0とArgument1が整数)は3つ、それ以外は1つ
+pub(crate) fn target_len(args: &[String]) -> usize {
+    let is_separated = args.first().is_some_and(|v| v.parse::<i32>().is_ok())
+        && args.get(1).is_some_and(|v| v.parse::<u32>().is_ok());
+    if is_separated {
+        3
+    } else {
+        1
+    }
+}
+
+/// 引数の先頭から指定された日付と、時刻を含む指定(Unix時間、`now`)ならその時点を読み取る。
+/// `is_julian`のときは、年月日をユリウス暦として読み取る。
+/// Unix時間と`today`・`now`は、`now`のタイムゾーンで日付にする。
+pub(crate) fn parse_target<Tz: TimeZone>(
+    args: &[String],
+    now: &DateTime<Tz>,
+    is_julian: bool,
+) -> Result<(NaiveDate, Option<DateTime<FixedOffset>>), String> {
+    let first = match args.first() {
+        Some(v) => v.as_str(),
+        None => return Err("arguments are not enough. a target date is required.".to_string()),
+    };
+
+    if target_len(args) == 3 {
+        return parse_separated(args, is_julian).map(|d| (d, None));
+    }
+
+    let form = detect_form(first).ok_or_else(|| {
+        if is_bare_number(first) {
+            format!(
+                "target date '{}' is ambiguous. only 8 digits are read as compact (%Y%m%d). \
+                 use '@{}' for a unix timestamp (@seconds).",
+                first, first
+            )
+        } else {
+            format!(
+                "target date '{}' is not in a known format. \
+                 use '%Y-%m-%d', '%Y/%m/%d', '%Y%m%d', '@' and a unix timestamp or 'today'.",
+                first
+            )
+        }
+    })?;
+
+    let instant = match form {
+        TargetForm::Timestamp => first
+            .trim_start_matches('@')
+            .parse::<i64>()
+            .ok()
+            .and_then(|v| DateTime::from_timestamp(v, 0))
+            .map(|v| v.with_timezone(&now.timezone())),
+        TargetForm::Now => Some(now.clone()),
+        _ => None,
+    };
+
+    let date = match form {
+        TargetForm::Iso => parse_splitted(first, '-', is_julian),
+        TargetForm::Slashed => parse_splitted(first, '/', is_julian),
+        TargetForm::Compact => first
+            .get(..4)
+            .zip(first.get(4..6))
+            .zip(first.get(6..))
+            .and_then(|((y, m), d)| {
+                from_ymd(y.parse().ok()?, m.parse().ok()?, d.parse().ok()?, is_julian)
+            }),
+        TargetForm::Timestamp | TargetForm::Now => instant.as_ref().map(|v| v.date_naive()),
+        TargetForm::Today => Some(now.date_naive()),
+    };
+
+    date.map(|d| (d, instant.map(|v| v.fixed_offset())))
+        .ok_or_else(|| format!("target date '{}' is invalid as {}.", first, form))
+}
+
+/// 時刻の引数(`12:00`、`01:25:30`、`03:00Z`、`12:00+09:00`)から、`date`のその時点を求める。
+/// 時差がなければ日本標準時とする
+pub(crate) fn parse_target_time(
+    s: &str,
+    date: &NaiveDate,
+) -> Result<DateTime<FixedOffset>, String> {
+    span::parse_time(s)
+        .and_then(|(time, offset)| date.and_time(time).and_local_timezone(offset).single())
+        .ok_or_else(|| {
+            format!(
+                "target time '{}' is invalid. the format is '%H:%M[:%S][+09:00]'.",
+                s
+            )
+        })
+}
+
+/// 年・月・日を別々の引数で指定したときの日付
+fn parse_separated(args: &[String], is_julian: bool) -> Result<NaiveDate, String> {
+    let (year, month, day) = match (args.first(), args.get(1), args.get(2)) {
+        (Some(year_str), Some(month_str), Some(day_str)) => {
+            match (
+                year_str.parse::<i32>(),
+                month_str.parse::<u32>(),
+                day_str.parse::<u32>(),
+            ) {
+                (Ok(y), Ok(m), Ok(d)) => (y, m, d),
+                (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => return Err(e.to_string()),
+            }
+        }
+        _ => return Err("arguments are not enough. arguments are required >= 3.".to_string()),
+    };
+
+    from_ymd(year, month, day, is_julian).ok_or_else(|| "target date is invalid.".to_string())
+}
+
+/// 年月日から日付を作る。`is_julian`のときはユリウス暦として扱う
+fn from_ymd(year: i32, month: u32, day: u32, is_julian: bool) -> Option<NaiveDate> {
+    if is_julian {
+        JulianDate::new(year, month, day).to_gregorian()
+    } else {
+        NaiveDate::from_ymd_opt(year, month, day)
+    }
+}
+
+/// 一つの文字列の形式を判別する。どの形式でもなければ`None`
+fn detect_form(s: &str) -> Option<TargetForm> {
+    let unsigned = s.strip_prefix('-').unwrap_or(s);
+    let is_digits = |v: &str| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit());
+
+    if s == "today" {
+        Some(TargetForm::Today)
+    } else if s == "now" {
+        Some(TargetForm::Now)
+    } else if unsigned.split('-').count() == 3 && unsigned.split('-').all(is_digits) {
+        Some(TargetForm::Iso)
+    } else if unsigned.split('/').count() == 3 && unsigned.split('/').all(is_digits) {
+        Some(TargetForm::Slashed)
+    } else if s.len() == 8 && is_digits(s) {
+        Some(TargetForm::Compact)
+    } else if s.strip_prefix('@').is_some_and(is_bare_number) {
+        Some(TargetForm::Timestamp)
+    } else {
+        None
+    }
+}
+
+/// 符号を除いて数字のみの文字列か。年とUnix時間の区別がつかないため、8桁以外は日付にしない
+fn is_bare_number(s: &str) -> bool {
+    let unsigned = s.strip_prefix('-').unwrap_or(s);
+    !unsigned.is_empty() && unsigned.chars().all(|c| c.is_ascii_digit())
+}
+
+/// `separator`で区切られた年月日。年は負の数でもよい
+fn parse_splitted(s: &str, separator: char, is_julian: bool) -> Option<NaiveDate> {
+    let (sign, unsigned) = match s.strip_prefix('-') {
+        Some(v) => (-1, v),
+        None => (1, s),
+    };
+    let mut parts = unsigned.split(separator);
+    let year = parts.next()?.parse::<i32>().ok()? * sign;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+
+    from_ymd(year, month, day, is_julian)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, Utc};
+
+    use super::*;
+
+    fn to_args(s: &[&str]) -> Vec<String> {
+        s.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn now() -> DateTime<FixedOffset> {
+        FixedOffset::east_opt(9 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2023, 10, 1, 12, 0, 0)
+            .unwrap()
+    }
+
+    fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    mod parse_target_time {
+        use super::*;
+
+        #[test]
+        fn success_when_valid_time() {
+            let date = ymd(1926, 12, 25);
+            assert_eq!(
+                parse_target_time("01:00", &date),
+                Ok(FixedOffset::east_opt(9 * 3600)
+                    .unwrap()
+                    .with_ymd_and_hms(1926, 12, 25, 1, 0, 0)
+                    .unwrap())
+            );
+            assert_eq!(
+                parse_target_time("16:00:30Z", &date),
+                Ok(Utc
+                    .with_ymd_and_hms(1926, 12, 25, 16, 0, 30)
+                    .unwrap()
+                    .fixed_offset())
+            );
+            assert_eq!(
+                parse_target_time("01:00-05:00", &date),
+                Ok(Utc
+                    .with_ymd_and_hms(1926, 12, 25, 6, 0, 0)
+                    .unwrap()
+                    .fixed_offset())
+            );
+        }
+
+        #[test]
+        fn failed_when_invalid_time() {
+            assert_eq!(
+                parse_target_time("25:00", &ymd(1926, 12, 25)),
+                Err(
+                    "target time '25:00' is invalid. the format is '%H:%M[:%S][+09:00]'."
+                        .to_string()
+                )
+            );
+            assert!(parse_target_time("01:00+9", &ymd(1926, 12, 25)).is_err());
+        }
+    }
+
+    mod target_len {
+        use super::*;
+
+        #[test]
+        fn checking_value() {
+            assert_eq!(target_len(&to_args(&["2023", "10", "1", "Gk"])), 3);
+            assert_eq!(target_len(&to_args(&["2023", "10"])), 3);
+            assert_eq!(target_len(&to_args(&["2023-10-01", "Gk"])), 1);
+            assert_eq!(target_len(&to_args(&["1696086000", "10"])), 3);
+            assert_eq!(target_len(&to_args(&["1696086000"])), 1);
+            assert_eq!(target_len(&to_args(&[])), 1);
+        }
+    }
+
+    mod parse_target {
+        use super::*;
+
+        #[test]
+        fn success_when_separated() {
+            assert_eq!(
+                parse_target(&to_args(&["2023", "10", "1", "Gk"]), &now(), false),
+                Ok((ymd(2023, 10, 1), None))
+            );
+            assert_eq!(
+                parse_target(&to_args(&["-50", "01", "01"]), &now(), false),
+                Ok((ymd(-50, 1, 1), None))
+            );
+        }
+
+        #[test]
+        fn success_when_single_string() {
+            assert_eq!(
+                parse_target(&to_args(&["2023-10-01", "Gk"]), &now(), false),
+                Ok((ymd(2023, 10, 1), None))
+            );
+            assert_eq!(
+                parse_target(&to_args(&["-50-1-1"]), &now(), false),
+                Ok((ymd(-50, 1, 1), None))
+            );
+            assert_eq!(
+                parse_target(&to_args(&["2023/10/01"]), &now(), false),
+                Ok((ymd(2023, 10, 1), None))
+            );
+            assert_eq!(
+                parse_target(&to_args(&["20231001", "Gk", "*_h"]), &now(), false),
+                Ok((ymd(2023, 10, 1), None))
+            );
+            assert_eq!(
+                parse_target(&to_args(&["today"]), &now(), false),
+                Ok((ymd(2023, 10, 1), None))
+            );
+            assert_eq!(
+                parse_target(&to_args(&["now", "10"]), &now(), false),
+                Ok((ymd(2023, 10, 1), Some(now())))
+            );
+        }
+
+        #[test]
+        fn success_when_timestamp() {
+            // 2023-09-30T15:00:00Z
+            let args = to_args(&["@1696086000", "Gk"]);
+            let instant = Utc
+                .with_ymd_and_hms(2023, 9, 30, 15, 0, 0)
+                .unwrap()
+                .fixed_offset();
+            assert_eq!(
+                parse_target(&args, &now(), false),
+                Ok((ymd(2023, 10, 1), Some(instant)))
+            );
+            assert_eq!(
+                parse_target(
+                    &args,
+                    &Utc.with_ymd_and_hms(2023, 10, 1, 0, 0, 0).unwrap(),
+                    false
+                ),
+                Ok((ymd(2023, 9, 30), Some(instant)))
+            );
+        }
+
+        #[test]
+        fn success_when_julian() {
+            let args = to_args(&["1582", "10", "4"]);
+            assert_eq!(
+                parse_target(&args, &now(), true),
+                Ok((ymd(1582, 10, 14), None))
+            );
+            assert_eq!(
+                parse_target(&to_args(&["1500-02-29"]), &now(), true),
+                Ok((ymd(1500, 3, 10), None))
+            );
+            // 年月日の指定でないものはユリウス暦に関係しない
+            assert_eq!(
+                parse_target(&to_args(&["today"]), &now(), true),
+                Ok((ymd(2023, 10, 1), None))
+            );
+            assert_eq!(
+                parse_target(&to_args(&["1500-02-29"]), &now(), false),
+                Err("target date '1500-02-29' is invalid as ISO 8601 (%Y-%m-%d).".to_string())
+            );
+        }
+
+        #[test]
+        fn failed_when_not_enough() {
+            assert_eq!(
+                parse_target(&to_args(&[]), &now(), false),
+                Err("arguments are not enough. a target date is required.".to_string())
+            );
+            assert_eq!(
+                parse_target(&to_args(&["2023", "10"]), &now(), false),
+                Err("arguments are not enough. arguments are required >= 3.".to_string())
+            );
+        }
+
+        #[test]
+        fn failed_with_detected_form() {
+            assert_eq!(
+                parse_target(&to_args(&["2023", "10", "x"]), &now(), false),
+                Err("invalid digit found in string".to_string())
+            );
+            assert_eq!(
+                parse_target(&to_args(&["2023", "2", "30"]), &now(), false),
+                Err("target date is invalid.".to_string())
+            );
+            assert_eq!(
+                parse_target(&to_args(&["2023-02-30"]), &now(), false),
+                Err("target date '2023-02-30' is invalid as ISO 8601 (%Y-%m-%d).".to_string())
+            );
+            assert_eq!(
+                parse_target(&to_args(&["2023/13/01"]), &now(), false),
+                Err("target date '2023/13/01' is invalid as slashed (%Y/%m/%d).".to_string())
+            );
+            assert_eq!(
+                parse_target(&to_args(&["20231301"]), &now(), false),
+                Err("target date '20231301' is invalid as compact (%Y%m%d).".to_string())
+            );
+            assert_eq!(
+                parse_target(&to_args(&["@99999999999999999"]), &now(), false),
+                Err(
+                    "target date '@99999999999999999' is invalid as unix timestamp (@seconds)."
+                        .to_string()
+                )
+            );
+            assert_eq!(
+                parse_target(&to_args(&["2023.10.01"]), &now(), false),
+                Err("target date '2023.10.01' is not in a known format. \
+                     use '%Y-%m-%d', '%Y/%m/%d', '%Y%m%d', '@' and a unix timestamp or 'today'."
+                    .to_string())
+            );
+        }
+
+        #[test]
+        fn failed_when_bare_number() {
+            assert_eq!(
+                parse_target(&to_args(&["2023"]), &now(), false),
+                Err(
+                    "target date '2023' is ambiguous. only 8 digits are read as compact (%Y%m%d). \
+                     use '@2023' for a unix timestamp (@seconds)."
+                        .to_string()
+                )
+            );
+            assert_eq!(
+                parse_target(&to_args(&["1696086000", "Gk"]), &now(), false),
+                Err("target date '1696086000' is ambiguous. \
+                     only 8 digits are read as compact (%Y%m%d). \
+                     use '@1696086000' for a unix timestamp (@seconds)."
+                    .to_string())
+            );
+        }
+    }
+}
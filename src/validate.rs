@@ -0,0 +1,492 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::NaiveDate;
+
+use crate::span::{self, Span};
+
+/// 検証で見つかった問題の種類
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum IssueKind {
+    /// ファイルを読み込めない
+    Unreadable,
+    /// 元号の行が一つもない
+    Empty,
+    /// 行の要素が足りない
+    InvalidFormat,
+    /// 日付の書式が正しくない
+    InvalidDate,
+    /// 属性が`key=value`の形式になっていない
+    InvalidAttribute,
+    /// 終了日が開始日より前になっている
+    EndBeforeStart,
+    /// 指定した行の期間と重なっている
+    Overlap(usize),
+    /// 指定した行の期間との間に空きがある
+    Gap(usize),
+    /// 指定したファイルと同じ範囲で、頭文字が重複している
+    DuplicateInitial(String),
+    /// 先頭のコメントの範囲が、実際の範囲と異なる
+    HeaderMismatch,
+    /// 指定したファイルと範囲が重なっている
+    RangeOverlap(String),
+    /// `****`が最後の行以外で使われている
+    MisplacedNow,
+}
+
+/// 検証で見つかった問題
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ValidationIssue {
+    file: String,
+    line: usize,
+    column: usize,
+    kind: IssueKind,
+    text: String,
+}
+
+impl Display for IssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IssueKind::Unreadable => write!(f, "unreadable"),
+            IssueKind::Empty => write!(f, "empty"),
+            IssueKind::InvalidFormat => write!(f, "invalid-format"),
+            IssueKind::InvalidDate => write!(f, "invalid-date"),
+            IssueKind::InvalidAttribute => write!(f, "invalid-attribute"),
+            IssueKind::EndBeforeStart => write!(f, "end-before-start"),
+            IssueKind::Overlap(line) => write!(f, "overlap({})", line),
+            IssueKind::Gap(line) => write!(f, "gap({})", line),
+            IssueKind::DuplicateInitial(file) => write!(f, "duplicate-initial({})", file),
+            IssueKind::HeaderMismatch => write!(f, "header-mismatch"),
+            IssueKind::RangeOverlap(file) => write!(f, "range-overlap({})", file),
+            IssueKind::MisplacedNow => write!(f, "misplaced-now"),
+        }
+    }
+}
+
+impl ValidationIssue {
+    pub fn new(
+        file: String,
+        line: usize,
+        column: usize,
+        kind: IssueKind,
+        text: String,
+    ) -> ValidationIssue {
+        ValidationIssue {
+            file,
+            line,
+            column,
+            kind,
+            text,
+        }
+    }
+
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+/// `ファイル名:行:列:種類:該当箇所`の形式で表す
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}:{}:{}",
+            self.file, self.line, self.column, self.kind, self.text
+        )
+    }
+}
+
+/// `path`(`gengou_lists`)内の全てのファイルを検証し、見つかった問題を全て返す
+pub(crate) fn validate_spans(path: &Path) -> Result<Vec<ValidationIssue>, std::io::Error> {
+    let mut issues = Vec::new();
+    for (file, entry) in list_dirs(path)
+        .iter()
+        .map(|(prefix, dir)| target_files_with_prefix(dir, prefix))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+    {
+        match fs::read_to_string(&entry) {
+            Ok(contents) => issues.extend(validate_contents(&file, &contents)),
+            Err(e) => issues.push(ValidationIssue::new(
+                file,
+                0,
+                0,
+                IssueKind::Unreadable,
+                e.to_string(),
+            )),
+        }
+    }
+
+    Ok(issues)
+}
+
+/// 検証の対象となるディレクトリ(`gengou_lists`と、あれば天皇の一覧)と、ファイル名に付ける接頭辞
+pub(crate) fn list_dirs(path: &Path) -> Vec<(String, PathBuf)> {
+    let mut dirs = vec![(String::new(), path.to_path_buf())];
+    let tennou_path = path.join(span::TENNOU_DIR_PATH);
+    if tennou_path.is_dir() {
+        dirs.push((format!("{}/", span::TENNOU_DIR_PATH), tennou_path));
+    }
+    dirs
+}
+
+fn target_files_with_prefix(
+    path: &Path,
+    prefix: &str,
+) -> Result<Vec<(String, PathBuf)>, std::io::Error> {
+    Ok(target_files(path)?
+        .into_iter()
+        .map(|(file, entry)| (format!("{}{}", prefix, file), entry))
+        .collect())
+}
+
+/// `path`(`gengou_lists`)内の読み込み対象のファイルを、ファイル名の順に返す
+pub(crate) fn target_files(path: &Path) -> Result<Vec<(String, PathBuf)>, std::io::Error> {
+    let mut entries = Vec::new();
+    for entry in path.read_dir()? {
+        entries.push(entry?.path());
+    }
+    entries.sort();
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let filestem = if let Some(s) = entry.file_stem() {
+            s.to_string_lossy()
+        } else {
+            continue;
+        };
+        if !span::is_target_filestem(&filestem)
+            || span::get_initial_char_after_underbar(&filestem).is_none()
+        {
+            continue;
+        }
+
+        let file = entry
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        files.push((file, entry));
+    }
+
+    Ok(files)
+}
+
+/// ファイルの内容を検証する
+fn validate_contents(file: &str, contents: &str) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let issue = |line, column, kind, text: &str| {
+        ValidationIssue::new(file.to_string(), line, column, kind, text.to_string())
+    };
+
+    // (行, 開始日の列, 開始日の文字列, 期間)
+    let mut spans: Vec<(usize, usize, &str, Span)> = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        let fields = if let Some(f) = span::split_line(line) {
+            f
+        } else {
+            continue;
+        };
+
+        let [(_, gengou), (start_column, start_str), (end_column, end_str), ..] = fields[..] else {
+            let text: Vec<&str> = fields.iter().map(|(_, v)| *v).collect();
+            issues.push(issue(
+                line_number,
+                fields[0].0,
+                IssueKind::InvalidFormat,
+                &text.join(","),
+            ));
+            continue;
+        };
+
+        for (column, field) in fields.iter().skip(6).filter(|(_, v)| !v.is_empty()) {
+            // 数え方は決まった値のみ
+            let is_valid = match span::parse_attribute(field) {
+                Some(("rule", value)) => span::parse_counting_rule(value).is_some(),
+                Some(_) => true,
+                None => false,
+            };
+            if !is_valid {
+                issues.push(issue(
+                    line_number,
+                    *column,
+                    IssueKind::InvalidAttribute,
+                    field,
+                ));
+            }
+        }
+
+        // 前後の期間との関係は日付のみで確かめる
+        let start = span::parse_boundary(start_str).map(|(d, _)| d);
+        if start.is_err() {
+            issues.push(issue(
+                line_number,
+                start_column,
+                IssueKind::InvalidDate,
+                start_str,
+            ));
+        }
+        let end = span::parse_boundary(end_str).map(|(d, _)| d);
+        if end.is_err() {
+            issues.push(issue(
+                line_number,
+                end_column,
+                IssueKind::InvalidDate,
+                end_str,
+            ));
+        }
+        let (start, end) = if let (Ok(s), Ok(e)) = (start, end) {
+            (s, e)
+        } else {
+            continue;
+        };
+
+        if end < start {
+            issues.push(issue(
+                line_number,
+                end_column,
+                IssueKind::EndBeforeStart,
+                end_str,
+            ));
+            spans.push((
+                line_number,
+                start_column,
+                start_str,
+                Span::new(gengou.to_string(), end, start),
+            ));
+        } else {
+            spans.push((
+                line_number,
+                start_column,
+                start_str,
+                Span::new(gengou.to_string(), start, end),
+            ));
+        }
+    }
+
+    if spans.is_empty() && issues.is_empty() {
+        issues.push(issue(0, 0, IssueKind::Empty, ""));
+    }
+
+    // 前後の期間との関係を確かめる
+    // 長い期間が後の短い期間を含むこともあるので、それまでで最も遅い終了日と比べる
+    spans.sort_by(|a, b| a.3.span().cmp(b.3.span()));
+    let mut latest: Option<(usize, NaiveDate)> = None;
+    for (line_number, start_column, start_str, current) in spans.iter() {
+        let start = current.span().start();
+
+        if let Some((prev_line, prev_end)) = latest {
+            // 終了日と次の開始日は同じ日でも、翌日でもよい
+            if *start < prev_end {
+                issues.push(issue(
+                    *line_number,
+                    *start_column,
+                    IssueKind::Overlap(prev_line),
+                    start_str,
+                ));
+            } else if prev_end.succ_opt().is_some_and(|next| *start > next) {
+                issues.push(issue(
+                    *line_number,
+                    *start_column,
+                    IssueKind::Gap(prev_line),
+                    start_str,
+                ));
+            }
+        }
+
+        let end = *current.span().end();
+        if latest.is_none_or(|(_, prev_end)| end > prev_end) {
+            latest = Some((*line_number, end));
+        }
+    }
+
+    issues.sort_by_key(|v| (v.line, v.column));
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod validate_spans {
+        use super::*;
+
+        #[test]
+        fn success_and_return_empty_when_bundled_lists() {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(span::SPAN_DIR_PATH);
+            let result = validate_spans(&path).unwrap();
+            assert_eq!(result, vec![]);
+        }
+
+        #[test]
+        fn return_issues_of_all_files() {
+            let path = std::env::temp_dir()
+                .join(format!("japanesegengou_validate_{}", std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("001_a.txt"), "a,2000_01-01,2000-12-31\n").unwrap();
+            fs::write(path.join("002_b.txt"), "b,2001-01-01\n").unwrap();
+            fs::write(path.join("readme.txt"), "not target\n").unwrap();
+            fs::create_dir_all(path.join(span::TENNOU_DIR_PATH)).unwrap();
+            fs::write(
+                path.join(span::TENNOU_DIR_PATH).join("001_a.txt"),
+                "a,2000-01-01,2000-12-31,,,,reign\n",
+            )
+            .unwrap();
+
+            let result: Vec<String> = validate_spans(&path)
+                .unwrap()
+                .iter()
+                .map(|v| v.to_string())
+                .collect();
+
+            assert_eq!(
+                result,
+                vec![
+                    "001_a.txt:1:3:invalid-date:2000_01-01".to_string(),
+                    "002_b.txt:1:1:invalid-format:b,2001-01-01".to_string(),
+                    "tennou/001_a.txt:1:28:invalid-attribute:reign".to_string(),
+                ]
+            );
+
+            fs::remove_dir_all(&path).unwrap();
+        }
+
+        #[test]
+        fn failed_when_directory_does_not_exist() {
+            let path = std::env::temp_dir().join("japanesegengou_validate_does_not_exist");
+            assert!(validate_spans(&path).is_err());
+        }
+    }
+
+    mod validate_contents {
+        use super::*;
+
+        #[test]
+        fn success_and_return_empty_when_valid_contents() {
+            let case = r#"
+// 645-08-01_686-08-17
+大化,645-08-01,650-03-25
+白雉,650-03-25,655-02-14,,,,rule=立年改元
+,655-02-15,686-08-17 // 空白期間
+"#;
+            assert_eq!(validate_contents("001_a.txt", case), vec![]);
+        }
+
+        #[test]
+        fn return_invalid_attribute_when_unknown_rule() {
+            let case = "大化,645-08-01,650-03-25,,,,rule=ritsunen,rule=other\n";
+            assert_eq!(
+                validate_contents("001_a.txt", case),
+                vec![ValidationIssue::new(
+                    "001_a.txt".to_string(),
+                    1,
+                    41,
+                    IssueKind::InvalidAttribute,
+                    "rule=other".to_string()
+                )]
+            );
+        }
+
+        #[test]
+        fn return_all_issues_with_location() {
+            let case = r#"
+大化,645-08-01,650-03-25
+白雉, 650-03-25, 650_02-14
+朱鳥,686-08-17,686-08-14,,,,rule
+,655-02-15
+"#;
+            let result = validate_contents("001_a.txt", case);
+            assert_eq!(
+                result,
+                vec![
+                    ValidationIssue::new(
+                        "001_a.txt".to_string(),
+                        3,
+                        16,
+                        IssueKind::InvalidDate,
+                        "650_02-14".to_string()
+                    ),
+                    ValidationIssue::new(
+                        "001_a.txt".to_string(),
+                        4,
+                        4,
+                        IssueKind::Gap(2),
+                        "686-08-17".to_string()
+                    ),
+                    ValidationIssue::new(
+                        "001_a.txt".to_string(),
+                        4,
+                        14,
+                        IssueKind::EndBeforeStart,
+                        "686-08-14".to_string()
+                    ),
+                    ValidationIssue::new(
+                        "001_a.txt".to_string(),
+                        4,
+                        27,
+                        IssueKind::InvalidAttribute,
+                        "rule".to_string()
+                    ),
+                    ValidationIssue::new(
+                        "001_a.txt".to_string(),
+                        5,
+                        1,
+                        IssueKind::InvalidFormat,
+                        ",655-02-15".to_string()
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn return_overlap_when_spans_overlap() {
+            let case = r#"
+白雉,650-03-25,655-02-14
+大化,645-08-01,650-04-01
+"#;
+            let result = validate_contents("001_a.txt", case);
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].line, 2);
+            assert_eq!(&result[0].kind, &IssueKind::Overlap(3));
+        }
+
+        #[test]
+        fn return_overlap_not_gap_when_long_span_contains_others() {
+            let case = r#"
+a,2000-01-01,2000-12-31
+b,2000-02-01,2000-03-01
+c,2000-05-01,2000-06-01
+"#;
+            let result = validate_contents("001_a.txt", case);
+            let kinds: Vec<(usize, &IssueKind)> =
+                result.iter().map(|v| (v.line, &v.kind)).collect();
+            // cはbの後にあるが、aと重なるのでgapではない
+            assert_eq!(
+                kinds,
+                vec![(3, &IssueKind::Overlap(2)), (4, &IssueKind::Overlap(2))]
+            );
+        }
+
+        #[test]
+        fn return_empty_issue_when_no_spans() {
+            let case = "// 大化,645-08-01,650-03-25\n";
+            let result = validate_contents("001_a.txt", case);
+            assert_eq!(result.len(), 1);
+            assert_eq!(&result[0].kind, &IssueKind::Empty);
+        }
+    }
+}